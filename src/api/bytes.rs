@@ -1,7 +1,20 @@
-use reqwest::{Client, Error};
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use reqwest::{Body, Client, Error, Response, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tokio_util::io::StreamReader;
+
+use crate::api::headers::{read_upload_result, with_download_options, with_redundant_upload_options};
+use crate::api::progress::{observed_body, report_tag, ProgressObserver};
+use crate::error::Error as BeeError;
+use crate::utils::stream::reader_stream;
 
 const ENDPOINT: &str = "bytes";
+const DOWNLOAD_ATTEMPTS: u32 = 3;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadResult {
@@ -12,7 +25,7 @@ pub struct UploadResult {
     pub history_address: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct UploadOptions {
     pub act: Option<bool>,
     #[serde(rename = "actHistoryAddress")]
@@ -23,7 +36,7 @@ pub struct UploadOptions {
     pub deferred: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct RedundantUploadOptions {
     #[serde(flatten)]
     pub upload_options: UploadOptions,
@@ -37,81 +50,147 @@ pub async fn upload(
     data: Vec<u8>,
     postage_batch_id: &str,
     options: Option<RedundantUploadOptions>,
+) -> Result<UploadResult, Error> {
+    upload_body(client, base_url, data.into(), None, postage_batch_id, options).await
+}
+
+/// Upload data and report the progress of the upload to `observer`.
+///
+/// The number of bytes sent is reported while the data is streamed to the
+/// node. If the node assigned a tag to the upload, its state is reported
+/// once the upload is done; use [`watch_tag`](crate::api::progress::watch_tag)
+/// to keep following it until the data is synced.
+pub async fn upload_with_progress(
+    client: &Client,
+    base_url: &str,
+    data: Vec<u8>,
+    postage_batch_id: &str,
+    options: Option<RedundantUploadOptions>,
+    observer: Arc<dyn ProgressObserver>,
+) -> Result<UploadResult, BeeError> {
+    let size = data.len() as u64;
+    let body = observed_body(data, observer.clone());
+    let result = upload_body(client, base_url, body, Some(size), postage_batch_id, options).await?;
+    report_tag(client, base_url, &result, observer.as_ref()).await?;
+    Ok(result)
+}
+
+/// Upload the content of `reader` without loading it into memory.
+///
+/// ## Arguments
+///
+/// * `size` - The size of the content, sent as `content-length`. Without it
+///   the body is sent with chunked transfer encoding.
+pub async fn upload_from_reader(
+    client: &Client,
+    base_url: &str,
+    reader: impl AsyncRead + Send + 'static,
+    size: Option<u64>,
+    postage_batch_id: &str,
+    options: Option<RedundantUploadOptions>,
+) -> Result<UploadResult, Error> {
+    let body = Body::wrap_stream(reader_stream(reader, size));
+    upload_body(client, base_url, body, size, postage_batch_id, options).await
+}
+
+/// Upload the content of a file without loading it into memory.
+pub async fn upload_from_file(
+    client: &Client,
+    base_url: &str,
+    file: tokio::fs::File,
+    postage_batch_id: &str,
+    options: Option<RedundantUploadOptions>,
+) -> Result<UploadResult, BeeError> {
+    let size = file.metadata().await?.len();
+    Ok(upload_from_reader(client, base_url, file, Some(size), postage_batch_id, options).await?)
+}
+
+pub(crate) async fn upload_body(
+    client: &Client,
+    base_url: &str,
+    body: Body,
+    size: Option<u64>,
+    postage_batch_id: &str,
+    options: Option<RedundantUploadOptions>,
 ) -> Result<UploadResult, Error> {
     let url = format!("{}/{}", base_url, ENDPOINT);
-    let mut request_builder = client.post(&url).body(data);
+    let mut request_builder = client.post(&url).body(body);
 
     request_builder = request_builder.header("content-type", "application/octet-stream");
+    if let Some(size) = size {
+        request_builder = request_builder.header("content-length", size.to_string());
+    }
     request_builder = request_builder.header("swarm-postage-batch-id", postage_batch_id);
 
-    if let Some(opts) = options {
-        if let Some(act) = opts.upload_options.act {
-            request_builder = request_builder.header("swarm-act", act.to_string());
-        }
-        if let Some(act_history_address) = opts.upload_options.act_history_address {
-            request_builder = request_builder.header("swarm-act-history-address", act_history_address);
-        }
-        if let Some(pin) = opts.upload_options.pin {
-            request_builder = request_builder.header("swarm-pin", pin.to_string());
-        }
-        if let Some(encrypt) = opts.upload_options.encrypt {
-            request_builder = request_builder.header("swarm-encrypt", encrypt.to_string());
-        }
-        if let Some(tag) = opts.upload_options.tag {
-            request_builder = request_builder.header("swarm-tag", tag.to_string());
-        }
-        if let Some(deferred) = opts.upload_options.deferred {
-            request_builder = request_builder.header("swarm-deferred", deferred.to_string());
-        }
-        if let Some(redundancy_level) = opts.redundancy_level {
-            request_builder = request_builder.header("swarm-redundancy-level", redundancy_level.to_string());
-        }
+    if let Some(opts) = &options {
+        request_builder = with_redundant_upload_options(request_builder, opts);
     }
 
     let response = request_builder.send().await?;
-
-    // Check for swarm-tag and swarm-act-history-address headers
-    let tag_uid = response
-        .headers()
-        .get("swarm-tag")
-        .and_then(|value| value.to_str().ok())
-        .and_then(|s| s.parse::<u32>().ok());
-
-    let history_address = response
-        .headers()
-        .get("swarm-act-history-address")
-        .and_then(|value| value.to_str().ok())
-        .map(|s| s.to_string());
-
-    let mut upload_result: UploadResult = response.json().await?;
-    upload_result.tag_uid = tag_uid;
-    upload_result.history_address = history_address;
-
-    Ok(upload_result)
+    read_upload_result(response).await
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReferenceInformation {
+    /// The size of the content, which Bee does not send for chunks.
     #[serde(rename = "contentLength")]
-    pub content_length: u64,
+    pub content_length: Option<u64>,
+    #[serde(rename = "contentType")]
+    pub content_type: Option<String>,
 }
 
+/// Retrieve the size and content type of data without downloading it.
+///
+/// Returns [`BeeError::NotFound`] if the reference is not known to the node.
 pub async fn head(
     client: &Client,
     base_url: &str,
     reference: &str,
-) -> Result<ReferenceInformation, Error> {
+) -> Result<ReferenceInformation, BeeError> {
     let url = format!("{}/{}/{}", base_url, ENDPOINT, reference);
     let response = client.head(&url).send().await?;
+    read_reference_information(response, reference)
+}
+
+/// Check whether data is known to the node, e.g. to skip uploading it again.
+pub async fn exists(client: &Client, base_url: &str, reference: &str) -> Result<bool, BeeError> {
+    found(head(client, base_url, reference).await)
+}
+
+/// Turn the not-found error of a `HEAD` request into `false`.
+pub(crate) fn found(information: Result<ReferenceInformation, BeeError>) -> Result<bool, BeeError> {
+    match information {
+        Ok(_) => Ok(true),
+        Err(BeeError::NotFound(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read the reference information from the headers of a `HEAD` response.
+pub(crate) fn read_reference_information(
+    response: Response,
+    reference: &str,
+) -> Result<ReferenceInformation, BeeError> {
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(BeeError::NotFound(reference.to_string()));
+    }
+    let response = response.error_for_status()?;
 
     let content_length = response
         .headers()
         .get("content-length")
         .and_then(|value| value.to_str().ok())
-        .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(0);
+        .and_then(|s| s.parse::<u64>().ok());
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_str().ok())
+        .map(|s| s.to_string());
 
-    Ok(ReferenceInformation { content_length })
+    Ok(ReferenceInformation {
+        content_length,
+        content_type,
+    })
 }
 
 pub async fn download(
@@ -123,34 +202,148 @@ pub async fn download(
     let url = format!("{}/{}/{}", base_url, ENDPOINT, resource);
     let mut request_builder = client.get(&url);
 
-    if let Some(opts) = options {
-        if let Some(redundancy_strategy) = opts.redundancy_strategy {
-            request_builder = request_builder.header("swarm-redundancy-strategy", redundancy_strategy.to_string());
-        }
-        if let Some(fallback) = opts.fallback {
-            request_builder = request_builder.header("swarm-fallback", fallback.to_string());
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
+    }
+
+    let response = request_builder.send().await?;
+    let bytes = response.bytes().await?;
+
+    Ok(bytes.to_vec())
+}
+
+/// Download data as a stream instead of loading it into memory.
+pub async fn download_readable(
+    client: &Client,
+    base_url: &str,
+    resource: &str,
+    options: Option<DownloadOptions>,
+) -> Result<ReadableDownload, Error> {
+    let url = format!("{}/{}/{}", base_url, ENDPOINT, resource);
+    let mut request_builder = client.get(&url);
+
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
+    }
+
+    let response = request_builder.send().await?.error_for_status()?;
+    Ok(ReadableDownload::from_response(response))
+}
+
+/// Download part of the data.
+///
+/// The node answers with `206 Partial Content` and the returned
+/// [`ReadableDownload::content_range`] describes the part that was sent.
+pub async fn download_range(
+    client: &Client,
+    base_url: &str,
+    resource: &str,
+    range: ByteRange,
+    options: Option<DownloadOptions>,
+) -> Result<ReadableDownload, Error> {
+    let url = format!("{}/{}/{}", base_url, ENDPOINT, resource);
+    let mut request_builder = client.get(&url).header("range", range.to_string());
+
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
+    }
+
+    let response = request_builder.send().await?.error_for_status()?;
+    Ok(ReadableDownload::from_response(response))
+}
+
+/// Download data into the file at `path`.
+///
+/// If the file already holds the beginning of the data, for example after an
+/// interrupted download, only the rest is requested and appended. The size of
+/// the data is taken from [`head`] and the file is checked against it once the
+/// download is done. Returns the size of the file.
+pub async fn download_to_file(
+    client: &Client,
+    base_url: &str,
+    resource: &str,
+    path: impl AsRef<Path>,
+    options: Option<DownloadOptions>,
+) -> Result<u64, BeeError> {
+    let path = path.as_ref();
+    let size = head(client, base_url, resource)
+        .await?
+        .content_length
+        .ok_or_else(|| BeeError::Custom(format!("no content length for {}", resource)))?;
+
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let mut offset = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        if offset > size {
+            // Not a prefix of the data, start over.
+            offset = 0;
         }
-        if let Some(timeout_ms) = opts.timeout_ms {
-            request_builder = request_builder.header("swarm-timeout", timeout_ms.to_string());
+        if offset == size && size > 0 {
+            return Ok(size);
         }
-        if let Some(act_publisher) = opts.act_publisher {
-            request_builder = request_builder.header("swarm-act-publisher", act_publisher);
+
+        let mut download = if offset == 0 {
+            download_readable(client, base_url, resource, options.clone()).await?
+        } else {
+            download_range(client, base_url, resource, ByteRange::from_offset(offset), options.clone()).await?
+        };
+        // The node may ignore the range and send the whole content. Content
+        // from anywhere else cannot be appended, so start over.
+        if offset > 0 && download.content_range.is_some_and(|range| range.start != offset) {
+            offset = 0;
+            download = download_readable(client, base_url, resource, options.clone()).await?;
         }
-        if let Some(act_history_address) = opts.act_history_address {
-            request_builder = request_builder.header("swarm-act-history-address", act_history_address);
+        if let Some(range) = download.content_range.filter(|range| range.start != offset) {
+            return Err(BeeError::Custom(format!(
+                "received {} from byte {}, expected byte {}",
+                resource, range.start, offset
+            )));
         }
-        if let Some(act_timestamp) = opts.act_timestamp {
-            request_builder = request_builder.header("swarm-act-timestamp", act_timestamp.to_string());
+        let append = offset > 0 && download.content_range.is_some();
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)
+            .await?;
+
+        let mut stream = download.stream;
+        let mut interrupted = None;
+        while let Some(piece) = stream.next().await {
+            match piece {
+                Ok(piece) => file.write_all(&piece).await?,
+                Err(e) => {
+                    interrupted = Some(e);
+                    break;
+                }
+            }
         }
-    }
+        file.flush().await?;
 
-    let response = request_builder.send().await?;
-    let bytes = response.bytes().await?;
+        match interrupted {
+            Some(_) if attempts < DOWNLOAD_ATTEMPTS => continue,
+            Some(e) => return Err(e.into()),
+            None => {}
+        }
 
-    Ok(bytes.to_vec())
+        let written = file.metadata().await?.len();
+        if written != size {
+            return Err(BeeError::Custom(format!(
+                "downloaded {} bytes of {}, expected {}",
+                written, resource, size
+            )));
+        }
+        return Ok(written);
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct DownloadOptions {
     #[serde(rename = "redundancyStrategy")]
     pub redundancy_strategy: Option<u8>,
@@ -165,8 +358,109 @@ pub struct DownloadOptions {
     pub act_timestamp: Option<u64>,
 }
 
-// downloadReadable is not directly translatable to a simple Rust function returning a stream
-// as reqwest::Response already provides a stream of bytes. The `download` function above
-// effectively covers the use case of downloading the entire content.
-// If a streaming interface is strictly required, it would involve returning `reqwest::Response`
-// and letting the caller handle the stream, or using a custom stream type.
+/// A range of bytes to download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    /// The last byte of the range, inclusive. Without it the range extends to
+    /// the end of the content.
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// The bytes from `start` to `end`, both inclusive.
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end: Some(end) }
+    }
+
+    /// The bytes from `start` to the end of the content.
+    pub fn from_offset(start: u64) -> Self {
+        Self { start, end: None }
+    }
+}
+
+impl std::fmt::Display for ByteRange {
+    /// Formats the range as the value of a `Range` header.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "bytes={}-{}", self.start, end),
+            None => write!(f, "bytes={}-", self.start),
+        }
+    }
+}
+
+/// The part of the content sent in a `206 Partial Content` response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    pub start: u64,
+    /// The last byte sent, inclusive.
+    pub end: u64,
+    /// The size of the whole content, if known.
+    pub size: Option<u64>,
+}
+
+impl ContentRange {
+    /// Parse a `Content-Range` header value such as `bytes 0-99/1000`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (range, size) = value.strip_prefix("bytes ")?.split_once('/')?;
+        let (start, end) = range.split_once('-')?;
+        Some(Self {
+            start: start.trim().parse().ok()?,
+            end: end.trim().parse().ok()?,
+            size: size.trim().parse().ok(),
+        })
+    }
+}
+
+/// Content being downloaded, together with the headers describing it.
+pub struct ReadableDownload {
+    pub content_type: Option<String>,
+    /// The file name, for files downloaded through `/bzz`.
+    pub file_name: Option<String>,
+    pub content_length: Option<u64>,
+    /// The part of the content being downloaded, for range requests.
+    pub content_range: Option<ContentRange>,
+    /// The content, read from the connection as it is polled.
+    pub stream: BoxStream<'static, Result<Bytes, Error>>,
+}
+
+impl ReadableDownload {
+    pub(crate) fn from_response(response: Response) -> Self {
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let content_type = header("content-type");
+        let file_name = header("swarm-file-name");
+        let content_length = response.content_length();
+        let content_range = header("content-range").and_then(|value| ContentRange::parse(&value));
+
+        Self {
+            content_type,
+            file_name,
+            content_length,
+            content_range,
+            stream: response.bytes_stream().boxed(),
+        }
+    }
+
+    /// Read the content through [`AsyncRead`], for example to copy it into a
+    /// file with [`tokio::io::copy`].
+    pub fn into_async_read(self) -> impl AsyncRead + Send + Unpin {
+        StreamReader::new(self.stream.map_err(std::io::Error::other))
+    }
+}
+
+impl std::fmt::Debug for ReadableDownload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadableDownload")
+            .field("content_type", &self.content_type)
+            .field("file_name", &self.file_name)
+            .field("content_length", &self.content_length)
+            .field("content_range", &self.content_range)
+            .finish_non_exhaustive()
+    }
+}
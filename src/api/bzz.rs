@@ -1,11 +1,32 @@
-use reqwest::{Client, Error};
+use futures::{StreamExt, TryStreamExt};
+use reqwest::{Body, Client, Error};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+
+use crate::api::headers::{read_upload_result, with_download_options, with_redundant_upload_options};
+use crate::api::manifest::{Manifest, CONTENT_TYPE_KEY, FILENAME_KEY};
+use crate::api::progress::{observed_body, report_tag, ProgressObserver};
+use crate::error::Error as BeeError;
+use crate::utils::collection::{detect_index_document, IgnoreRules};
+use crate::utils::collection_node::make_collection_from_fs;
+use crate::utils::merkle_tree::{file_address, file_address_from_path};
+use crate::utils::mime;
+use crate::utils::stream::reader_stream;
+use crate::utils::tar_uploader::upload_tar;
 
 const BZZ_ENDPOINT: &str = "bzz";
+const HEAD_CONCURRENCY: usize = 8;
+const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 4;
+const DEFAULT_UPLOAD_CONCURRENCY: usize = 4;
 
 // Reusing UploadResult and DownloadOptions from bytes module
-use crate::api::bytes::{DownloadOptions, RedundantUploadOptions, UploadResult};
+use crate::api::bytes::{
+    self, found, read_reference_information, ByteRange, DownloadOptions, ReadableDownload, RedundantUploadOptions,
+    ReferenceInformation, UploadResult,
+};
 
 #[derive(Debug, Serialize, Default)]
 pub struct FileUploadOptions {
@@ -26,6 +47,58 @@ pub struct CollectionUploadOptions {
     pub error_document: Option<String>,
 }
 
+#[derive(Debug, Serialize, Default)]
+pub struct DirectoryUploadOptions {
+    #[serde(flatten)]
+    pub collection_upload_options: CollectionUploadOptions,
+    /// Patterns in `.gitignore` format of paths to leave out.
+    pub ignore: Vec<String>,
+    #[serde(rename = "followSymlinks")]
+    pub follow_symlinks: bool,
+    /// The number of files uploaded in parallel, 4 by default.
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct CollectionDownloadOptions {
+    #[serde(flatten)]
+    pub download_options: DownloadOptions,
+    /// The number of files downloaded in parallel, 4 by default.
+    pub concurrency: Option<usize>,
+    /// Skip files that already exist with the same content.
+    #[serde(rename = "skipExisting")]
+    pub skip_existing: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CollectionDownloadReport {
+    pub downloaded: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct SyncOptions {
+    #[serde(flatten)]
+    pub redundant_upload_options: RedundantUploadOptions,
+    /// Patterns in `.gitignore` format of paths to leave out.
+    pub ignore: Vec<String>,
+    #[serde(rename = "followSymlinks")]
+    pub follow_symlinks: bool,
+    /// The number of files hashed and uploaded in parallel, 4 by default.
+    pub concurrency: Option<usize>,
+}
+
+/// The changes made to a manifest by [`sync_directory`].
+#[derive(Debug, Default, Clone)]
+pub struct SyncReport {
+    /// The reference of the updated manifest.
+    pub reference: String,
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct FileData {
     pub name: Option<String>,
@@ -37,10 +110,53 @@ pub struct FileData {
     pub data: Vec<u8>,
 }
 
+#[derive(Debug, Clone)]
+pub enum CollectionSource {
+    /// Content held in memory.
+    Data(Vec<u8>),
+    /// A file on disk, read while the collection is uploaded.
+    File(PathBuf),
+}
+
 #[derive(Debug, Serialize)]
 pub struct CollectionEntry {
     pub path: String,
     pub size: u64,
+    #[serde(skip)] // Content is streamed separately
+    pub source: CollectionSource,
+}
+
+impl CollectionEntry {
+    /// An entry whose content is held in memory.
+    pub fn from_data(path: &str, data: Vec<u8>) -> Self {
+        Self {
+            path: path.to_string(),
+            size: data.len() as u64,
+            source: CollectionSource::Data(data),
+        }
+    }
+
+    /// An entry whose content is read from `file` during the upload.
+    pub fn from_file(path: &str, file: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let file = file.into();
+        let size = std::fs::metadata(&file)?.len();
+        Ok(Self {
+            path: path.to_string(),
+            size,
+            source: CollectionSource::File(file),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionFile {
+    pub path: String,
+    pub reference: String,
+    #[serde(rename = "contentType")]
+    pub content_type: Option<String>,
+    #[serde(rename = "fileName")]
+    pub file_name: Option<String>,
+    pub size: u64,
 }
 
 pub async fn upload_file(
@@ -50,9 +166,76 @@ pub async fn upload_file(
     postage_batch_id: &str,
     name: Option<&str>,
     options: Option<FileUploadOptions>,
+) -> Result<UploadResult, Error> {
+    upload_file_body(client, base_url, data.into(), postage_batch_id, name, options).await
+}
+
+/// Upload a file and report the progress of the upload to `observer`.
+///
+/// See [`bytes::upload_with_progress`] for the reported progress.
+pub async fn upload_file_with_progress(
+    client: &Client,
+    base_url: &str,
+    data: Vec<u8>,
+    postage_batch_id: &str,
+    name: Option<&str>,
+    options: Option<FileUploadOptions>,
+    observer: Arc<dyn ProgressObserver>,
+) -> Result<UploadResult, BeeError> {
+    let mut options = options.unwrap_or_default();
+    options.size.get_or_insert(data.len() as u64);
+    let body = observed_body(data, observer.clone());
+    let result = upload_file_body(client, base_url, body, postage_batch_id, name, Some(options)).await?;
+    report_tag(client, base_url, &result, observer.as_ref()).await?;
+    Ok(result)
+}
+
+/// Upload the content of `reader` as a file without loading it into memory.
+///
+/// The size in the options is sent as `content-length` and exactly that many
+/// bytes are read. Without it the body is sent with chunked transfer
+/// encoding.
+pub async fn upload_file_from_reader(
+    client: &Client,
+    base_url: &str,
+    reader: impl AsyncRead + Send + 'static,
+    postage_batch_id: &str,
+    name: Option<&str>,
+    options: Option<FileUploadOptions>,
+) -> Result<UploadResult, Error> {
+    let size = options.as_ref().and_then(|options| options.size);
+    let body = Body::wrap_stream(reader_stream(reader, size));
+    upload_file_body(client, base_url, body, postage_batch_id, name, options).await
+}
+
+/// Upload a file from disk without loading it into memory.
+///
+/// The size is taken from the file unless set in the options.
+pub async fn upload_file_from_file(
+    client: &Client,
+    base_url: &str,
+    file: tokio::fs::File,
+    postage_batch_id: &str,
+    name: Option<&str>,
+    options: Option<FileUploadOptions>,
+) -> Result<UploadResult, BeeError> {
+    let mut options = options.unwrap_or_default();
+    if options.size.is_none() {
+        options.size = Some(file.metadata().await?.len());
+    }
+    Ok(upload_file_from_reader(client, base_url, file, postage_batch_id, name, Some(options)).await?)
+}
+
+pub(crate) async fn upload_file_body(
+    client: &Client,
+    base_url: &str,
+    body: Body,
+    postage_batch_id: &str,
+    name: Option<&str>,
+    options: Option<FileUploadOptions>,
 ) -> Result<UploadResult, Error> {
     let url = format!("{}/{}", base_url, BZZ_ENDPOINT);
-    let mut request_builder = client.post(&url).body(data);
+    let mut request_builder = client.post(&url).body(body);
 
     request_builder = request_builder.header("swarm-postage-batch-id", postage_batch_id);
 
@@ -63,30 +246,7 @@ pub async fn upload_file(
         if let Some(size) = opts.size {
             request_builder = request_builder.header("content-length", size.to_string());
         }
-
-        // Handle RedundantUploadOptions
-        let redundant_opts = opts.redundant_upload_options;
-        if let Some(act) = redundant_opts.upload_options.act {
-            request_builder = request_builder.header("swarm-act", act.to_string());
-        }
-        if let Some(act_history_address) = redundant_opts.upload_options.act_history_address {
-            request_builder = request_builder.header("swarm-act-history-address", act_history_address);
-        }
-        if let Some(pin) = redundant_opts.upload_options.pin {
-            request_builder = request_builder.header("swarm-pin", pin.to_string());
-        }
-        if let Some(encrypt) = redundant_opts.upload_options.encrypt {
-            request_builder = request_builder.header("swarm-encrypt", encrypt.to_string());
-        }
-        if let Some(tag) = redundant_opts.upload_options.tag {
-            request_builder = request_builder.header("swarm-tag", tag.to_string());
-        }
-        if let Some(deferred) = redundant_opts.upload_options.deferred {
-            request_builder = request_builder.header("swarm-deferred", deferred.to_string());
-        }
-        if let Some(redundancy_level) = redundant_opts.redundancy_level {
-            request_builder = request_builder.header("swarm-redundancy-level", redundancy_level.to_string());
-        }
+        request_builder = with_redundant_upload_options(request_builder, &opts.redundant_upload_options);
     }
 
     if let Some(n) = name {
@@ -94,24 +254,7 @@ pub async fn upload_file(
     }
 
     let response = request_builder.send().await?;
-
-    let tag_uid = response
-        .headers()
-        .get("swarm-tag")
-        .and_then(|value| value.to_str().ok())
-        .and_then(|s| s.parse::<u32>().ok());
-
-    let history_address = response
-        .headers()
-        .get("swarm-act-history-address")
-        .and_then(|value| value.to_str().ok())
-        .map(|s| s.to_string());
-
-    let mut upload_result: UploadResult = response.json().await?;
-    upload_result.tag_uid = tag_uid;
-    upload_result.history_address = history_address;
-
-    Ok(upload_result)
+    read_upload_result(response).await
 }
 
 pub async fn download_file(
@@ -128,25 +271,8 @@ pub async fn download_file(
 
     let mut request_builder = client.get(&url);
 
-    if let Some(opts) = options {
-        if let Some(redundancy_strategy) = opts.redundancy_strategy {
-            request_builder = request_builder.header("swarm-redundancy-strategy", redundancy_strategy.to_string());
-        }
-        if let Some(fallback) = opts.fallback {
-            request_builder = request_builder.header("swarm-fallback", fallback.to_string());
-        }
-        if let Some(timeout_ms) = opts.timeout_ms {
-            request_builder = request_builder.header("swarm-timeout", timeout_ms.to_string());
-        }
-        if let Some(act_publisher) = opts.act_publisher {
-            request_builder = request_builder.header("swarm-act-publisher", act_publisher);
-        }
-        if let Some(act_history_address) = opts.act_history_address {
-            request_builder = request_builder.header("swarm-act-history-address", act_history_address);
-        }
-        if let Some(act_timestamp) = opts.act_timestamp {
-            request_builder = request_builder.header("swarm-act-timestamp", act_timestamp.to_string());
-        }
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
     }
 
     let response = request_builder.send().await?;
@@ -173,5 +299,415 @@ pub async fn download_file(
     })
 }
 
-// TODO: Implement upload_collection which involves tar archiving
-// For now, download_file_readable is not implemented as it returns a stream
+/// Download a file as a stream instead of loading it into memory.
+///
+/// ## Arguments
+///
+/// * `path` - The path of the file inside a collection.
+pub async fn download_file_readable(
+    client: &Client,
+    base_url: &str,
+    reference: &str,
+    path: Option<&str>,
+    options: Option<DownloadOptions>,
+) -> Result<ReadableDownload, Error> {
+    let mut url = format!("{}/{}/{}", base_url, BZZ_ENDPOINT, reference);
+    if let Some(p) = path {
+        url = format!("{}/{}", url, p);
+    }
+
+    let mut request_builder = client.get(&url);
+
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
+    }
+
+    let response = request_builder.send().await?.error_for_status()?;
+    Ok(ReadableDownload::from_response(response))
+}
+
+/// Retrieve the size and content type of a file without downloading it.
+///
+/// Returns [`BeeError::NotFound`] if the file or the path inside the
+/// collection does not exist.
+pub async fn head(
+    client: &Client,
+    base_url: &str,
+    reference: &str,
+    path: Option<&str>,
+) -> Result<ReferenceInformation, BeeError> {
+    let mut url = format!("{}/{}/{}", base_url, BZZ_ENDPOINT, reference);
+    let mut resource = reference.to_string();
+    if let Some(p) = path {
+        url = format!("{}/{}", url, p);
+        resource = format!("{}/{}", reference, p);
+    }
+
+    let response = client.head(&url).send().await?;
+    read_reference_information(response, &resource)
+}
+
+/// Check whether a file or a path inside a collection exists.
+pub async fn exists(client: &Client, base_url: &str, reference: &str, path: Option<&str>) -> Result<bool, BeeError> {
+    found(head(client, base_url, reference, path).await)
+}
+
+/// Download part of a file.
+///
+/// See [`bytes::download_range`] for the returned content range.
+pub async fn download_file_range(
+    client: &Client,
+    base_url: &str,
+    reference: &str,
+    path: Option<&str>,
+    range: ByteRange,
+    options: Option<DownloadOptions>,
+) -> Result<ReadableDownload, Error> {
+    let mut url = format!("{}/{}/{}", base_url, BZZ_ENDPOINT, reference);
+    if let Some(p) = path {
+        url = format!("{}/{}", url, p);
+    }
+
+    let mut request_builder = client.get(&url).header("range", range.to_string());
+
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
+    }
+
+    let response = request_builder.send().await?.error_for_status()?;
+    Ok(ReadableDownload::from_response(response))
+}
+
+/// Walk the manifest of a collection and list every file it contains.
+///
+/// The size of each file is taken from a `HEAD /bytes` request on its
+/// reference.
+pub async fn list_collection(
+    client: &Client,
+    base_url: &str,
+    reference: &str,
+) -> Result<Vec<CollectionFile>, BeeError> {
+    let mut manifest = Manifest::load(client, base_url, reference).await?;
+    let entries = manifest.entries().await?;
+
+    futures::stream::iter(entries)
+        .map(|entry| async move {
+            let size = bytes::head(client, base_url, &entry.reference)
+                .await?
+                .content_length
+                .ok_or_else(|| BeeError::Custom(format!("no content length for {}", entry.path)))?;
+            Ok::<_, BeeError>(CollectionFile {
+                content_type: entry.metadata.get(CONTENT_TYPE_KEY).cloned(),
+                file_name: entry.metadata.get(FILENAME_KEY).cloned(),
+                path: entry.path,
+                reference: entry.reference,
+                size,
+            })
+        })
+        .buffered(HEAD_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+/// Upload a collection of files, streamed to Bee as a tar archive.
+///
+/// Files on disk are read while the request body is sent, so the archive is
+/// never held in memory as a whole.
+pub async fn upload_collection(
+    client: &Client,
+    base_url: &str,
+    entries: Vec<CollectionEntry>,
+    postage_batch_id: &str,
+    options: Option<CollectionUploadOptions>,
+) -> Result<UploadResult, BeeError> {
+    upload_tar(client, base_url, entries, postage_batch_id, options).await
+}
+
+/// Upload every file below a local directory as a collection.
+///
+/// The files are uploaded one by one and the manifest is built locally, so
+/// every entry gets the content type detected from its extension, the same
+/// as with [`sync_directory`]. `index.html` at the root of the directory is
+/// used as index document unless one is set in the options.
+///
+/// ACT protects a collection through Bee's collection upload, so ACT
+/// uploads are rejected; use [`upload_collection`] instead.
+pub async fn upload_files_from_directory(
+    client: &Client,
+    base_url: &str,
+    dir: impl AsRef<Path>,
+    postage_batch_id: &str,
+    options: Option<DirectoryUploadOptions>,
+) -> Result<UploadResult, BeeError> {
+    let options = options.unwrap_or_default();
+    let collection_options = options.collection_upload_options;
+    let upload_options = collection_options.redundant_upload_options;
+    if upload_options.upload_options.act == Some(true) {
+        return Err(BeeError::Custom("ACT directories must be uploaded with upload_collection".to_string()));
+    }
+    let concurrency = options.concurrency.unwrap_or(DEFAULT_UPLOAD_CONCURRENCY).max(1);
+
+    let dir = dir.as_ref().to_path_buf();
+    let ignore = IgnoreRules::new(&options.ignore);
+    let follow_symlinks = options.follow_symlinks;
+    let entries = tokio::task::spawn_blocking(move || {
+        make_collection_from_fs(&dir, &ignore, follow_symlinks)
+    })
+    .await
+    .map_err(|e| BeeError::Custom(format!("directory walk failed: {}", e)))??;
+
+    let index_document = collection_options
+        .index_document
+        .or_else(|| detect_index_document(&entries));
+
+    let references = upload_entries(client, base_url, &entries, postage_batch_id, &upload_options, concurrency).await?;
+    let mut manifest = Manifest::new(client, base_url);
+    for (entry, file_reference) in entries.iter().zip(references) {
+        let metadata = entry_metadata(&entry.path, BTreeMap::new());
+        manifest.add_file(&entry.path, &file_reference, metadata).await?;
+    }
+    if let Some(index_document) = index_document {
+        manifest.set_index_document(&index_document).await?;
+    }
+    if let Some(error_document) = collection_options.error_document {
+        manifest.set_error_document(&error_document).await?;
+    }
+
+    let reference = manifest.save(postage_batch_id, Some(upload_options)).await?;
+    Ok(UploadResult {
+        reference,
+        tag_uid: None,
+        history_address: None,
+    })
+}
+
+/// Bring the collection stored under `reference` in line with a local
+/// directory.
+///
+/// The references of the local files are calculated offline and compared
+/// with the entries of the manifest, so only new and changed files are
+/// uploaded. Entries without a local file are removed from the manifest.
+/// Returns the reference of the updated manifest, which is `reference`
+/// itself when nothing changed.
+///
+/// Encryption, ACT and erasure coding change the references of uploaded
+/// files, so they cannot be compared and are rejected.
+pub async fn sync_directory(
+    client: &Client,
+    base_url: &str,
+    dir: impl AsRef<Path>,
+    reference: &str,
+    postage_batch_id: &str,
+    options: Option<SyncOptions>,
+) -> Result<SyncReport, BeeError> {
+    let options = options.unwrap_or_default();
+    let upload_options = &options.redundant_upload_options.upload_options;
+    if upload_options.act == Some(true) || upload_options.encrypt == Some(true) {
+        return Err(BeeError::Custom("encrypted and ACT collections cannot be synced".to_string()));
+    }
+    if options.redundant_upload_options.redundancy_level.is_some_and(|level| level > 0) {
+        return Err(BeeError::Custom("erasure coded collections cannot be synced".to_string()));
+    }
+    let concurrency = options.concurrency.unwrap_or(DEFAULT_UPLOAD_CONCURRENCY).max(1);
+
+    let dir = dir.as_ref().to_path_buf();
+    let ignore = IgnoreRules::new(&options.ignore);
+    let follow_symlinks = options.follow_symlinks;
+    let local = tokio::task::spawn_blocking(move || {
+        make_collection_from_fs(&dir, &ignore, follow_symlinks)
+    })
+    .await
+    .map_err(|e| BeeError::Custom(format!("directory walk failed: {}", e)))??;
+
+    let mut manifest = Manifest::load(client, base_url, reference).await?;
+    let mut remote: HashMap<String, BTreeMap<String, String>> = HashMap::new();
+    let mut remote_references = HashMap::new();
+    for entry in manifest.entries().await? {
+        remote_references.insert(entry.path.clone(), entry.reference);
+        remote.insert(entry.path, entry.metadata);
+    }
+
+    let hashes: Vec<String> = futures::stream::iter(local.iter().map(entry_reference))
+        .buffered(concurrency)
+        .try_collect()
+        .await?;
+
+    let mut report = SyncReport::default();
+    let mut changed = Vec::new();
+    for (entry, hash) in local.into_iter().zip(hashes) {
+        match remote_references.remove(&entry.path) {
+            Some(existing) if existing == hash => report.unchanged.push(entry.path),
+            Some(_) => {
+                report.modified.push(entry.path.clone());
+                changed.push(entry);
+            }
+            None => {
+                report.added.push(entry.path.clone());
+                changed.push(entry);
+            }
+        }
+    }
+    report.removed = remote_references.into_keys().collect();
+    report.removed.sort();
+
+    let upload_options = &options.redundant_upload_options;
+    let references = upload_entries(client, base_url, &changed, postage_batch_id, upload_options, concurrency).await?;
+    for (entry, file_reference) in changed.iter().zip(references) {
+        let metadata = entry_metadata(&entry.path, remote.remove(&entry.path).unwrap_or_default());
+        manifest.add_file(&entry.path, &file_reference, metadata).await?;
+    }
+    for path in &report.removed {
+        manifest.remove(path).await?;
+    }
+
+    report.reference = if changed.is_empty() && report.removed.is_empty() {
+        reference.to_string()
+    } else {
+        manifest.save(postage_batch_id, Some(options.redundant_upload_options)).await?
+    };
+    Ok(report)
+}
+
+/// Upload the content of every entry and return the references in order.
+async fn upload_entries(
+    client: &Client,
+    base_url: &str,
+    entries: &[CollectionEntry],
+    postage_batch_id: &str,
+    options: &RedundantUploadOptions,
+    concurrency: usize,
+) -> Result<Vec<String>, BeeError> {
+    let uploads: Vec<_> = entries
+        .iter()
+        .map(|entry| async move {
+            let result = match &entry.source {
+                CollectionSource::Data(data) => {
+                    bytes::upload(client, base_url, data.clone(), postage_batch_id, Some(options.clone())).await?
+                }
+                CollectionSource::File(path) => {
+                    let file = tokio::fs::File::open(path).await?;
+                    bytes::upload_from_file(client, base_url, file, postage_batch_id, Some(options.clone())).await?
+                }
+            };
+            Ok::<_, BeeError>(result.reference)
+        })
+        .collect();
+    futures::stream::iter(uploads).buffered(concurrency).try_collect().await
+}
+
+/// Set the file name and the content type detected from the extension of
+/// the file at `path`, keeping any other entries of `metadata`.
+fn entry_metadata(path: &str, mut metadata: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    metadata.insert(FILENAME_KEY.to_string(), name.to_string());
+    match mime::content_type(path) {
+        Some(content_type) => metadata.insert(CONTENT_TYPE_KEY.to_string(), content_type.to_string()),
+        None => metadata.remove(CONTENT_TYPE_KEY),
+    };
+    metadata
+}
+
+async fn entry_reference(entry: &CollectionEntry) -> Result<String, BeeError> {
+    let address = match &entry.source {
+        CollectionSource::Data(data) => file_address(data),
+        CollectionSource::File(path) => file_address_from_path(path).await?,
+    };
+    Ok(hex::encode(address))
+}
+
+/// Download every file of a collection into a local directory.
+///
+/// Files are streamed to their path inside the collection and their size is
+/// checked against the size of the uploaded content. With `skip_existing`,
+/// files whose local content hashes to the reference of the entry are not
+/// downloaded again.
+pub async fn download_collection(
+    client: &Client,
+    base_url: &str,
+    reference: &str,
+    dir: impl AsRef<Path>,
+    options: Option<CollectionDownloadOptions>,
+) -> Result<CollectionDownloadReport, BeeError> {
+    let options = options.unwrap_or_default();
+    let dir = dir.as_ref();
+    let files = list_collection(client, base_url, reference).await?;
+
+    let mut targets = Vec::with_capacity(files.len());
+    for file in files {
+        let target = collection_file_path(dir, &file.path)?;
+        targets.push((file, target));
+    }
+
+    let concurrency = options.concurrency.unwrap_or(DEFAULT_DOWNLOAD_CONCURRENCY).max(1);
+    let results: Vec<(String, bool)> = futures::stream::iter(targets)
+        .map(|(file, target)| {
+            let options = &options;
+            async move {
+                if options.skip_existing && is_same_file(&target, &file).await {
+                    return Ok::<_, BeeError>((file.path, false));
+                }
+
+                let download = bytes::download_readable(
+                    client,
+                    base_url,
+                    &file.reference,
+                    Some(options.download_options.clone()),
+                )
+                .await?;
+                if let Some(parent) = target.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                let mut output = tokio::fs::File::create(&target).await?;
+                let written = tokio::io::copy(&mut download.into_async_read(), &mut output).await?;
+                output.flush().await?;
+                if written != file.size {
+                    return Err(BeeError::Custom(format!(
+                        "size of {} is {} bytes, expected {}",
+                        file.path, written, file.size
+                    )));
+                }
+                Ok((file.path, true))
+            }
+        })
+        .buffer_unordered(concurrency)
+        .try_collect()
+        .await?;
+
+    let mut report = CollectionDownloadReport::default();
+    for (path, downloaded) in results {
+        if downloaded {
+            report.downloaded.push(path);
+        } else {
+            report.skipped.push(path);
+        }
+    }
+    report.downloaded.sort();
+    report.skipped.sort();
+    Ok(report)
+}
+
+/// The local path of a collection entry, rejecting paths that would escape
+/// the target directory.
+fn collection_file_path(dir: &Path, path: &str) -> Result<PathBuf, BeeError> {
+    let mut target = dir.to_path_buf();
+    for component in path.split('/') {
+        let unsafe_component =
+            component.is_empty() || component == "." || component == ".." || component.contains('\\');
+        if unsafe_component {
+            return Err(BeeError::Custom(format!("unsafe path in collection: {}", path)));
+        }
+        target.push(component);
+    }
+    Ok(target)
+}
+
+async fn is_same_file(target: &Path, file: &CollectionFile) -> bool {
+    match tokio::fs::metadata(target).await {
+        Ok(metadata) if metadata.is_file() && metadata.len() == file.size => {}
+        _ => return false,
+    }
+    match file_address_from_path(target).await {
+        Ok(address) => hex::encode(address) == file.reference,
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing and verification of SWAP cheques.
+//! https://github.com/ethersphere/bee/blob/master/pkg/settlement/swap/chequebook/cheque.go
+//!
+//! A cheque promises the beneficiary the cumulative payout of a chequebook,
+//! so every new cheque replaces the previous one. Cheques are signed by the
+//! chequebook issuer as EIP-712 typed data of the `Cheque` struct in the
+//! `Chequebook` domain of the chain the chequebook lives on.
+
+use num_traits::Signed;
+use sha3::{Digest, Keccak256};
+
+use crate::api::debug::chequebook::Cheque as ChequeResponse;
+use crate::api::signer::{recover_hash_address, PrivateKeySigner, ADDRESS_SIZE, SIGNATURE_SIZE};
+use crate::error::Error;
+use crate::utils::tokens::BZZ;
+
+/// The chain ID of the Gnosis chain, on which mainnet chequebooks live.
+pub const GNOSIS_CHAIN_ID: u64 = 100;
+/// The chain ID of the Sepolia testnet.
+pub const SEPOLIA_CHAIN_ID: u64 = 11_155_111;
+
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId)";
+const DOMAIN_NAME: &str = "Chequebook";
+const DOMAIN_VERSION: &str = "1.0";
+const CHEQUE_TYPE: &str = "Cheque(address chequebook,address beneficiary,uint256 cumulativePayout)";
+
+/// An unsigned SWAP cheque.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cheque {
+    /// The chequebook contract paying out the cheque.
+    pub chequebook: [u8; ADDRESS_SIZE],
+    pub beneficiary: [u8; ADDRESS_SIZE],
+    /// The total amount paid to the beneficiary by this and all previous
+    /// cheques of the chequebook.
+    pub cumulative_payout: BZZ,
+}
+
+impl Cheque {
+    /// The EIP-712 hash of the cheque struct.
+    pub fn struct_hash(&self) -> Result<[u8; 32], Error> {
+        let mut hasher = Keccak256::new();
+        hasher.update(Keccak256::digest(CHEQUE_TYPE));
+        hasher.update(encode_address(&self.chequebook));
+        hasher.update(encode_address(&self.beneficiary));
+        hasher.update(encode_uint256(&self.cumulative_payout)?);
+        Ok(hasher.finalize().into())
+    }
+
+    /// The hash the issuer signs for the cheque on the chain `chain_id`,
+    /// `keccak256("\x19\x01" || domain separator || struct hash)`.
+    pub fn signing_hash(&self, chain_id: u64) -> Result<[u8; 32], Error> {
+        let mut hasher = Keccak256::new();
+        hasher.update([0x19, 0x01]);
+        hasher.update(domain_separator(chain_id));
+        hasher.update(self.struct_hash()?);
+        Ok(hasher.finalize().into())
+    }
+
+    /// Sign the cheque with the key of the chequebook issuer.
+    pub fn sign(self, signer: &PrivateKeySigner, chain_id: u64) -> Result<SignedCheque, Error> {
+        let signature = signer.sign_hash(&self.signing_hash(chain_id)?)?;
+        Ok(SignedCheque { cheque: self, signature })
+    }
+}
+
+impl TryFrom<&ChequeResponse> for Cheque {
+    type Error = Error;
+
+    /// Convert a cheque as reported by `BeeDebugChequebookClient`.
+    fn try_from(cheque: &ChequeResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chequebook: parse_address(&cheque.chequebook)?,
+            beneficiary: parse_address(&cheque.beneficiary)?,
+            cumulative_payout: cheque.payout.clone(),
+        })
+    }
+}
+
+/// A SWAP cheque with the signature of the chequebook issuer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedCheque {
+    pub cheque: Cheque,
+    pub signature: [u8; SIGNATURE_SIZE],
+}
+
+impl SignedCheque {
+    /// Recover the address of the issuer that signed the cheque on the chain
+    /// `chain_id`.
+    pub fn recover_issuer(&self, chain_id: u64) -> Result<[u8; ADDRESS_SIZE], Error> {
+        recover_hash_address(&self.cheque.signing_hash(chain_id)?, &self.signature)
+    }
+
+    /// Check that the cheque was signed by `issuer` on the chain `chain_id`.
+    pub fn verify(&self, chain_id: u64, issuer: &[u8; ADDRESS_SIZE]) -> Result<(), Error> {
+        let signer = self.recover_issuer(chain_id)?;
+        if signer != *issuer {
+            return Err(Error::Custom(format!(
+                "cheque signed by 0x{} instead of 0x{}",
+                hex::encode(signer),
+                hex::encode(issuer)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The EIP-712 domain separator of chequebooks on the chain `chain_id`.
+pub fn domain_separator(chain_id: u64) -> [u8; 32] {
+    let mut chain_id_word = [0u8; 32];
+    chain_id_word[24..].copy_from_slice(&chain_id.to_be_bytes());
+
+    let mut hasher = Keccak256::new();
+    hasher.update(Keccak256::digest(DOMAIN_TYPE));
+    hasher.update(Keccak256::digest(DOMAIN_NAME));
+    hasher.update(Keccak256::digest(DOMAIN_VERSION));
+    hasher.update(chain_id_word);
+    hasher.finalize().into()
+}
+
+/// Parse a hex encoded Ethereum address, with or without `0x`.
+pub fn parse_address(address: &str) -> Result<[u8; ADDRESS_SIZE], Error> {
+    let bytes = hex::decode(address.strip_prefix("0x").unwrap_or(address))
+        .map_err(|e| Error::Custom(format!("invalid address {}: {}", address, e)))?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| Error::Custom(format!("invalid address length: {}", bytes.len())))
+}
+
+fn encode_address(address: &[u8; ADDRESS_SIZE]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[32 - ADDRESS_SIZE..].copy_from_slice(address);
+    word
+}
+
+fn encode_uint256(amount: &BZZ) -> Result<[u8; 32], Error> {
+    let (_, bytes) = amount.to_plur().to_bytes_be();
+    if amount.to_plur().is_negative() || bytes.len() > 32 {
+        return Err(Error::Custom(format!("cumulative payout out of range: {}", amount)));
+    }
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(word)
+}
@@ -1,7 +1,12 @@
 use reqwest::{Client, Error};
 use serde::{Deserialize, Serialize};
 
-use crate::api::bytes::{DownloadOptions, UploadOptions, UploadResult};
+use crate::api::bytes::{
+    found, read_reference_information, DownloadOptions, ReadableDownload, ReferenceInformation, UploadOptions, UploadResult,
+};
+use crate::api::headers::{read_upload_result, with_download_options, with_postage, with_upload_options, Postage};
+use crate::api::stamper::Stamp;
+use crate::error::Error as BeeError;
 
 const ENDPOINT: &str = "chunks";
 
@@ -21,53 +26,59 @@ pub async fn upload(
     data: Vec<u8>,
     postage_batch_id: &str,
     options: Option<UploadOptions>,
+) -> Result<UploadResult, Error> {
+    upload_with_postage(client, base_url, data, Postage::BatchId(postage_batch_id), options).await
+}
+
+/// Upload a chunk with a stamp signed by the batch owner, for example with a
+/// [`Stamper`](crate::api::stamper::Stamper).
+///
+/// The node does not need to own the batch, so chunks can be uploaded
+/// through gateways.
+pub async fn upload_with_stamp(
+    client: &Client,
+    base_url: &str,
+    data: Vec<u8>,
+    stamp: &Stamp,
+    options: Option<UploadOptions>,
+) -> Result<UploadResult, Error> {
+    upload_with_postage(client, base_url, data, Postage::Stamp(stamp), options).await
+}
+
+async fn upload_with_postage(
+    client: &Client,
+    base_url: &str,
+    data: Vec<u8>,
+    postage: Postage<'_>,
+    options: Option<UploadOptions>,
 ) -> Result<UploadResult, Error> {
     let url = format!("{}/{}", base_url, ENDPOINT);
     let mut request_builder = client.post(&url).body(data);
 
     request_builder = request_builder.header("content-type", "application/octet-stream");
-    request_builder = request_builder.header("swarm-postage-batch-id", postage_batch_id);
-
-    if let Some(opts) = options {
-        if let Some(act) = opts.act {
-            request_builder = request_builder.header("swarm-act", act.to_string());
-        }
-        if let Some(act_history_address) = opts.act_history_address {
-            request_builder = request_builder.header("swarm-act-history-address", act_history_address);
-        }
-        if let Some(pin) = opts.pin {
-            request_builder = request_builder.header("swarm-pin", pin.to_string());
-        }
-        if let Some(encrypt) = opts.encrypt {
-            request_builder = request_builder.header("swarm-encrypt", encrypt.to_string());
-        }
-        if let Some(tag) = opts.tag {
-            request_builder = request_builder.header("swarm-tag", tag.to_string());
-        }
-        if let Some(deferred) = opts.deferred {
-            request_builder = request_builder.header("swarm-deferred", deferred.to_string());
-        }
+    request_builder = with_postage(request_builder, postage);
+
+    if let Some(opts) = &options {
+        request_builder = with_upload_options(request_builder, opts);
     }
 
     let response = request_builder.send().await?;
+    read_upload_result(response).await
+}
 
-    let tag_uid = response
-        .headers()
-        .get("swarm-tag")
-        .and_then(|value| value.to_str().ok())
-        .and_then(|s| s.parse::<u32>().ok());
-
-    let history_address = response
-        .headers()
-        .get("swarm-act-history-address")
-        .and_then(|value| value.to_str().ok())
-        .map(|s| s.to_string());
-
-    let mut upload_result: UploadResult = response.json().await?;
-    upload_result.tag_uid = tag_uid;
-    upload_result.history_address = history_address;
+/// Check whether the node has a chunk, without downloading it.
+///
+/// Returns [`BeeError::NotFound`] if the chunk is not stored locally.
+pub async fn head(client: &Client, base_url: &str, reference: &str) -> Result<ReferenceInformation, BeeError> {
+    let url = format!("{}/{}/{}", base_url, ENDPOINT, reference);
+    let response = client.head(&url).send().await?;
+    read_reference_information(response, reference)
+}
 
-    Ok(upload_result)
+/// Check whether the node stores a chunk locally, e.g. to skip uploading it
+/// again.
+pub async fn exists(client: &Client, base_url: &str, reference: &str) -> Result<bool, BeeError> {
+    found(head(client, base_url, reference).await)
 }
 
 pub async fn download(
@@ -79,25 +90,8 @@ pub async fn download(
     let url = format!("{}/{}/{}", base_url, ENDPOINT, reference);
     let mut request_builder = client.get(&url);
 
-    if let Some(opts) = options {
-        if let Some(redundancy_strategy) = opts.redundancy_strategy {
-            request_builder = request_builder.header("swarm-redundancy-strategy", redundancy_strategy.to_string());
-        }
-        if let Some(fallback) = opts.fallback {
-            request_builder = request_builder.header("swarm-fallback", fallback.to_string());
-        }
-        if let Some(timeout_ms) = opts.timeout_ms {
-            request_builder = request_builder.header("swarm-timeout", timeout_ms.to_string());
-        }
-        if let Some(act_publisher) = opts.act_publisher {
-            request_builder = request_builder.header("swarm-act-publisher", act_publisher);
-        }
-        if let Some(act_history_address) = opts.act_history_address {
-            request_builder = request_builder.header("swarm-act-history-address", act_history_address);
-        }
-        if let Some(act_timestamp) = opts.act_timestamp {
-            request_builder = request_builder.header("swarm-act-timestamp", act_timestamp.to_string());
-        }
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
     }
 
     let response = request_builder.send().await?;
@@ -105,3 +99,21 @@ pub async fn download(
 
     Ok(bytes.to_vec())
 }
+
+/// Download a chunk as a stream instead of loading it into memory.
+pub async fn download_readable(
+    client: &Client,
+    base_url: &str,
+    reference: &str,
+    options: Option<DownloadOptions>,
+) -> Result<ReadableDownload, Error> {
+    let url = format!("{}/{}/{}", base_url, ENDPOINT, reference);
+    let mut request_builder = client.get(&url);
+
+    if let Some(opts) = &options {
+        request_builder = with_download_options(request_builder, opts);
+    }
+
+    let response = request_builder.send().await?.error_for_status()?;
+    Ok(ReadableDownload::from_response(response))
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use url::{Url, ParseError as UrlParseError};
 use thiserror::Error;
 
+use crate::utils::tokens::BZZ;
+
 #[derive(Error, Debug)]
 pub enum BeeDebugBalanceError {
     #[error("Request error: {0}")]
@@ -17,7 +19,8 @@ pub enum BeeDebugBalanceError {
 #[serde(rename_all = "camelCase")]
 pub struct PeerBalance {
     pub peer: String,
-    pub balance: String,
+    /// Positive if the peer owes the node, negative if the node owes the peer.
+    pub balance: BZZ,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,224 @@
+//! Keeps postage batches alive and roomy without manual intervention.
+//!
+//! A [`BatchManager`] checks a batch against a [`BatchPolicy`]: it dilutes
+//! the batch when it fills up, tops it up when its time to live runs low, and
+//! waits until the node reports every change before making the next.
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use thiserror::Error;
+use url::ParseError as UrlParseError;
+
+use crate::api::debug::stamps::{BeeDebugStampsClient, BeeDebugStampsError, PostageBatch};
+use crate::api::debug::states::{BeeDebugStatesClient, BeeDebugStatesError};
+use crate::error::Error as BeeError;
+use crate::utils::size::Size;
+use crate::utils::stamps::{amount_for_ttl, estimate_batch, BLOCK_TIME};
+use crate::utils::tokens::BZZ;
+
+#[derive(Error, Debug)]
+pub enum BatchManagerError {
+    #[error("Stamps error: {0}")]
+    Stamps(#[from] BeeDebugStampsError),
+    #[error("Chain state error: {0}")]
+    States(#[from] BeeDebugStatesError),
+    #[error(transparent)]
+    Bee(#[from] BeeError),
+    #[error("URL parse error: {0}")]
+    UrlParse(#[from] UrlParseError),
+    #[error("Invalid policy: {0}")]
+    InvalidPolicy(String),
+}
+
+impl BatchManagerError {
+    /// Whether maintaining the batch again later may succeed, e.g. after the
+    /// node was unreachable or slow to apply a change.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Stamps(BeeDebugStampsError::Reqwest(e))
+            | Self::States(BeeDebugStatesError::Reqwest(e))
+            | Self::Bee(BeeError::Reqwest(e)) => match e.status() {
+                Some(status) => status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+                // No response at all, e.g. while the node restarts.
+                None => !e.is_decode(),
+            },
+            Self::Stamps(BeeDebugStampsError::NotUsable(..)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// When and how a [`BatchManager`] changes a batch.
+#[derive(Debug, Clone)]
+pub struct BatchPolicy {
+    /// Top up the batch when its time to live drops below this.
+    pub min_ttl: Duration,
+    /// The time to live to top the batch up to.
+    pub target_ttl: Duration,
+    /// Dilute the batch when its utilization in percent reaches this.
+    pub max_utilization_percent: f64,
+    /// The number of levels to deepen the batch by when diluting.
+    pub dilute_by: u8,
+    /// The depth the batch is never diluted beyond.
+    pub max_depth: u8,
+    /// How long to wait for the node to report a change and the batch to be
+    /// usable again.
+    pub usable_timeout: Duration,
+    /// How often to poll the batch while waiting.
+    pub poll_interval: Duration,
+}
+
+impl Default for BatchPolicy {
+    fn default() -> Self {
+        Self {
+            min_ttl: Duration::from_secs(24 * 60 * 60),
+            target_ttl: Duration::from_secs(7 * 24 * 60 * 60),
+            max_utilization_percent: 80.0,
+            dilute_by: 1,
+            max_depth: 30,
+            usable_timeout: Duration::from_secs(10 * 60),
+            poll_interval: BLOCK_TIME,
+        }
+    }
+}
+
+/// A change made to a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchAction {
+    /// The batch was topped up by `amount` per chunk.
+    ToppedUp { amount: BZZ },
+    /// The batch was diluted to `depth`.
+    Diluted { depth: u8 },
+}
+
+/// The outcome of [`BatchManager::maintain`].
+#[derive(Debug, Clone)]
+pub struct MaintenanceReport {
+    /// The state of the batch after the changes.
+    pub batch: PostageBatch,
+    pub actions: Vec<BatchAction>,
+}
+
+pub struct BatchManager {
+    stamps: BeeDebugStampsClient,
+    states: BeeDebugStatesClient,
+    policy: BatchPolicy,
+}
+
+impl BatchManager {
+    /// Create a manager for the node at `base_url`.
+    ///
+    /// Fails for a policy with a `dilute_by` of 0, which would dilute batches
+    /// to their current depth.
+    pub fn new(base_url: &str, policy: BatchPolicy) -> Result<Self, BatchManagerError> {
+        if policy.dilute_by == 0 {
+            return Err(BatchManagerError::InvalidPolicy("dilute_by must be at least 1".to_string()));
+        }
+        Ok(Self {
+            stamps: BeeDebugStampsClient::new(base_url)?,
+            states: BeeDebugStatesClient::new(base_url)?,
+            policy,
+        })
+    }
+
+    pub fn policy(&self) -> &BatchPolicy {
+        &self.policy
+    }
+
+    /// Buy a batch of `depth` that lives for the target time to live of the
+    /// policy and wait until it is usable.
+    pub async fn create(&self, depth: u8, label: Option<&str>) -> Result<PostageBatch, BatchManagerError> {
+        let amount = amount_for_ttl(self.policy.target_ttl, &self.current_price().await?, BLOCK_TIME)?;
+        self.buy(&amount, depth, label).await
+    }
+
+    /// Buy a batch that stores `size` for `ttl`, e.g. `"5GB"` for three
+    /// weeks, and wait until it is usable.
+    pub async fn create_for_size(
+        &self,
+        size: Size,
+        ttl: Duration,
+        label: Option<&str>,
+    ) -> Result<PostageBatch, BatchManagerError> {
+        let estimate = estimate_batch(size, ttl, &self.current_price().await?, BLOCK_TIME)?;
+        self.buy(&estimate.amount, estimate.depth, label).await
+    }
+
+    /// Dilute and top up a batch as required by the policy.
+    ///
+    /// The batch is diluted first, as diluting divides its time to live.
+    pub async fn maintain(&self, batch_id: &str) -> Result<MaintenanceReport, BatchManagerError> {
+        let mut batch = self.stamps.get_postage_batch(batch_id).await?;
+        let mut actions = Vec::new();
+
+        if batch.utilization_percent() >= self.policy.max_utilization_percent && batch.depth < self.policy.max_depth {
+            let depth = batch.depth.saturating_add(self.policy.dilute_by).min(self.policy.max_depth);
+            self.stamps.dilute_batch(batch_id, depth).await?;
+            actions.push(BatchAction::Diluted { depth });
+            // The top-up is based on the time to live of the diluted batch.
+            batch = self.wait_until(batch_id, |batch| batch.depth >= depth).await?;
+        }
+
+        // The node reports no time to live until it has synced the batch.
+        if let Some(ttl) = batch.ttl().filter(|ttl| *ttl < self.policy.min_ttl) {
+            let extension = self.policy.target_ttl.saturating_sub(ttl);
+            let amount = amount_for_ttl(extension, &self.current_price().await?, BLOCK_TIME)?;
+            self.stamps.top_up_batch(batch_id, &amount).await?;
+            actions.push(BatchAction::ToppedUp { amount });
+            let paid = batch.amount.clone();
+            batch = self.wait_until(batch_id, |batch| batch.amount > paid).await?;
+        }
+
+        Ok(MaintenanceReport { batch, actions })
+    }
+
+    /// Maintain a batch every `interval`, passing every report with changes
+    /// to `on_report` and every transient error to `on_error`.
+    ///
+    /// Returns on the first error that is not
+    /// [transient](BatchManagerError::is_transient), e.g. once the batch has
+    /// expired.
+    pub async fn monitor(
+        &self,
+        batch_id: &str,
+        interval: Duration,
+        on_report: impl Fn(&MaintenanceReport),
+        on_error: impl Fn(&BatchManagerError),
+    ) -> Result<(), BatchManagerError> {
+        loop {
+            match self.maintain(batch_id).await {
+                Ok(report) if !report.actions.is_empty() => on_report(&report),
+                Ok(_) => {}
+                Err(e) if e.is_transient() => on_error(&e),
+                Err(e) => return Err(e),
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn buy(&self, amount: &BZZ, depth: u8, label: Option<&str>) -> Result<PostageBatch, BatchManagerError> {
+        let batch_id = self
+            .stamps
+            .create_postage_batch(amount, depth, None, None, label)
+            .await?;
+        self.wait_until(batch_id.as_str(), |_| true).await
+    }
+
+    /// Wait until the batch is usable and satisfies `ready`.
+    async fn wait_until(
+        &self,
+        batch_id: &str,
+        ready: impl Fn(&PostageBatch) -> bool,
+    ) -> Result<PostageBatch, BatchManagerError> {
+        let batch = self
+            .stamps
+            .poll_until_usable(batch_id, self.policy.usable_timeout, self.policy.poll_interval, ready)
+            .await?;
+        Ok(batch)
+    }
+
+    async fn current_price(&self) -> Result<BZZ, BatchManagerError> {
+        Ok(self.states.get_chain_state().await?.current_price)
+    }
+}
@@ -5,6 +5,8 @@ use thiserror::Error;
 use std::collections::HashMap;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 
+use crate::utils::tokens::BZZ;
+
 #[derive(Error, Debug)]
 pub enum BeeDebugChequebookError {
     #[error("Request error: {0}")]
@@ -26,8 +28,8 @@ pub struct ChequebookAddressResponse {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChequebookBalanceResponse {
-    pub total_balance: String,
-    pub available_balance: String,
+    pub total_balance: BZZ,
+    pub available_balance: BZZ,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,14 +37,14 @@ pub struct ChequebookBalanceResponse {
 pub struct Cheque {
     pub beneficiary: String,
     pub chequebook: String,
-    pub payout: String,
+    pub payout: BZZ,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CashoutResult {
     pub recipient: String,
-    pub last_payout: String,
+    pub last_payout: BZZ,
     pub bounced: bool,
 }
 
@@ -50,7 +52,7 @@ pub struct CashoutResult {
 #[serde(rename_all = "camelCase")]
 pub struct LastCashoutActionResponse {
     pub peer: String,
-    pub uncashed_amount: String,
+    pub uncashed_amount: BZZ,
     pub transaction_hash: Option<String>,
     pub last_cashed_cheque: Option<Cheque>,
     pub result: Option<CashoutResult>,
@@ -131,12 +133,12 @@ impl BeeDebugChequebookClient {
         Ok(response.json().await?)
     }
 
-    pub async fn deposit_tokens(&self, amount: &str, gas_price: Option<&str>) -> Result<String, BeeDebugChequebookError> {
+    pub async fn deposit_tokens(&self, amount: &BZZ, gas_price: Option<&str>) -> Result<String, BeeDebugChequebookError> {
         let url = self.base_url.join("chequebook/deposit")?;
         let mut request = self.client.post(url);
 
         let mut params = HashMap::new();
-        params.insert("amount", amount);
+        params.insert("amount", amount.to_plur().to_string());
         request = request.query(&params);
 
         let mut headers = HeaderMap::new();
@@ -150,12 +152,12 @@ impl BeeDebugChequebookClient {
         Ok(body["transactionHash"].as_str().unwrap_or_default().to_string())
     }
 
-    pub async fn withdraw_tokens(&self, amount: &str, gas_price: Option<&str>) -> Result<String, BeeDebugChequebookError> {
+    pub async fn withdraw_tokens(&self, amount: &BZZ, gas_price: Option<&str>) -> Result<String, BeeDebugChequebookError> {
         let url = self.base_url.join("chequebook/withdraw")?;
         let mut request = self.client.post(url);
 
         let mut params = HashMap::new();
-        params.insert("amount", amount);
+        params.insert("amount", amount.to_plur().to_string());
         request = request.query(&params);
 
         let mut headers = HeaderMap::new();
@@ -6,3 +6,6 @@ pub mod connectivity;
 pub mod settlements;
 pub mod transactions;
 pub mod stamps;
+pub mod batch_manager;
+pub mod stake;
+pub mod redistribution;
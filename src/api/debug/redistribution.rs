@@ -0,0 +1,134 @@
+//! Flags staking nodes at risk of losing rewards in the redistribution game.
+//!
+//! A node is frozen, and cannot play for a while, when the reserve sample it
+//! commits to disagrees with its neighbourhood, which happens when it is out
+//! of sync or stores a different radius than its neighbours. A node whose
+//! reserve sampling takes too long misses the reveal phase.
+
+use std::time::Duration;
+
+use reqwest::{Client, Error as ReqwestError};
+use thiserror::Error;
+use url::{Url, ParseError as UrlParseError};
+
+use crate::api::debug::stake::{BeeDebugStakeClient, BeeDebugStakeError, RedistributionState};
+use crate::api::debug::status::{BeeDebugClient, BeeDebugClientError, DebugStatus};
+use crate::api::rchash::rchash;
+
+/// The longest reserve sampling that still fits into a round, as recommended
+/// by the Bee documentation.
+pub const SAMPLE_DURATION_LIMIT: Duration = Duration::from_secs(6 * 60);
+
+/// The anchors of the sample measured by [`RedistributionReporter::report`].
+const SAMPLE_ANCHOR: &str = "aaaa";
+
+#[derive(Error, Debug)]
+pub enum RedistributionError {
+    #[error("Stake error: {0}")]
+    Stake(#[from] BeeDebugStakeError),
+    #[error("Status error: {0}")]
+    Status(#[from] BeeDebugClientError),
+    #[error("Request error: {0}")]
+    Reqwest(#[from] ReqwestError),
+    #[error("URL parse error: {0}")]
+    UrlParse(#[from] UrlParseError),
+}
+
+/// A reason why a node may lose rewards.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RedistributionRisk {
+    /// The node is frozen and cannot play until the freeze ends.
+    Frozen { last_frozen_round: u64 },
+    /// The node has not synced its reserve and would commit to a wrong sample.
+    NotFullySynced,
+    /// The storage radius of the node differs from its neighbourhood.
+    Unhealthy { storage_radius: u32 },
+    /// The node cannot pay for the transactions of a round.
+    InsufficientFunds,
+    /// Sampling the reserve takes too long to reveal in time.
+    SlowSampling { duration: Duration, limit: Duration },
+}
+
+/// The redistribution status of a node.
+#[derive(Debug)]
+pub struct RedistributionReport {
+    pub state: RedistributionState,
+    pub storage_radius: u32,
+    /// The duration of the last reserve sampling, measured with `rchash` if
+    /// requested, otherwise as reported by the node.
+    pub sample_duration: Duration,
+    pub risks: Vec<RedistributionRisk>,
+}
+
+impl RedistributionReport {
+    pub fn new(state: RedistributionState, status: &DebugStatus, sample_duration: Option<Duration>) -> Self {
+        let sample_duration =
+            sample_duration.unwrap_or_else(|| Duration::from_secs_f64(state.last_sample_duration_seconds.max(0.0)));
+
+        let mut risks = Vec::new();
+        if state.is_frozen {
+            risks.push(RedistributionRisk::Frozen { last_frozen_round: state.last_frozen_round });
+        }
+        if !state.is_fully_synced {
+            risks.push(RedistributionRisk::NotFullySynced);
+        }
+        if !state.is_healthy {
+            risks.push(RedistributionRisk::Unhealthy { storage_radius: status.storage_radius });
+        }
+        if !state.has_sufficient_funds {
+            risks.push(RedistributionRisk::InsufficientFunds);
+        }
+        if sample_duration > SAMPLE_DURATION_LIMIT {
+            risks.push(RedistributionRisk::SlowSampling { duration: sample_duration, limit: SAMPLE_DURATION_LIMIT });
+        }
+
+        Self {
+            state,
+            storage_radius: status.storage_radius,
+            sample_duration,
+            risks,
+        }
+    }
+
+    /// Whether the node is at risk of being frozen or missing rounds.
+    pub fn is_at_risk(&self) -> bool {
+        !self.risks.is_empty()
+    }
+}
+
+pub struct RedistributionReporter {
+    client: Client,
+    base_url: Url,
+    stake: BeeDebugStakeClient,
+    status: BeeDebugClient,
+}
+
+impl RedistributionReporter {
+    pub fn new(base_url: &str) -> Result<Self, UrlParseError> {
+        Ok(Self {
+            client: Client::new(),
+            base_url: Url::parse(base_url)?,
+            stake: BeeDebugStakeClient::new(base_url)?,
+            status: BeeDebugClient::new(base_url)?,
+        })
+    }
+
+    /// Report the redistribution status of the node.
+    ///
+    /// With `measure_sampling`, the node samples its reserve at its storage
+    /// radius, which takes several minutes on a full node.
+    pub async fn report(&self, measure_sampling: bool) -> Result<RedistributionReport, RedistributionError> {
+        let state = self.stake.get_redistribution_state().await?;
+        let status = self.status.get_debug_status().await?;
+
+        let sample_duration = if measure_sampling {
+            let base_url = self.base_url.as_str().trim_end_matches('/');
+            let seconds = rchash(&self.client, base_url, status.storage_radius, SAMPLE_ANCHOR, SAMPLE_ANCHOR).await?;
+            Some(Duration::from_secs_f64(seconds.max(0.0)))
+        } else {
+            None
+        };
+
+        Ok(RedistributionReport::new(state, &status, sample_duration))
+    }
+}
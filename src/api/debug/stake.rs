@@ -0,0 +1,139 @@
+use reqwest::{Client, Error as ReqwestError, RequestBuilder};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use url::{Url, ParseError as UrlParseError};
+use thiserror::Error;
+
+use crate::api::typed_bytes::TransactionHash;
+use crate::utils::tokens::{BZZ, DAI};
+
+#[derive(Error, Debug)]
+pub enum BeeDebugStakeError {
+    #[error("Request error: {0}")]
+    Reqwest(#[from] ReqwestError),
+    #[error("URL parse error: {0}")]
+    UrlParse(#[from] UrlParseError),
+    #[error("Header error: {0}")]
+    Header(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeResponse {
+    pub staked_amount: BZZ,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawableStakeResponse {
+    /// The stake exceeding what the storage radius of the node requires.
+    pub withdrawable_amount: BZZ,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedistributionState {
+    /// The minimum balance needed to play a round.
+    pub minimum_gas_funds: DAI,
+    pub has_sufficient_funds: bool,
+    pub is_frozen: bool,
+    pub is_healthy: bool,
+    pub is_fully_synced: bool,
+    pub phase: RedistributionPhase,
+    pub round: u64,
+    pub last_won_round: u64,
+    pub last_played_round: u64,
+    pub last_frozen_round: u64,
+    pub last_selected_round: u64,
+    pub last_sample_duration_seconds: f64,
+    pub block: u64,
+    pub reward: BZZ,
+    pub fees: DAI,
+}
+
+/// The phase of the current redistribution round.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum RedistributionPhase {
+    Commit,
+    Reveal,
+    Claim,
+    Sample,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionResponse {
+    tx_hash: TransactionHash,
+}
+
+pub struct BeeDebugStakeClient {
+    client: Client,
+    base_url: Url,
+}
+
+impl BeeDebugStakeClient {
+    pub fn new(base_url: &str) -> Result<Self, UrlParseError> {
+        let base_url = Url::parse(base_url)?;
+        let client = Client::new();
+        Ok(Self { client, base_url })
+    }
+
+    pub async fn get_stake(&self) -> Result<StakeResponse, BeeDebugStakeError> {
+        let url = self.base_url.join("stake")?;
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn deposit_stake(&self, amount: &BZZ, gas_price: Option<&str>, gas_limit: Option<&str>) -> Result<TransactionHash, BeeDebugStakeError> {
+        let url = self.base_url.join(&format!("stake/{}", amount.to_plur()))?;
+        let request = with_gas_options(self.client.post(url), gas_price, gas_limit)?;
+        send_transaction(request).await
+    }
+
+    pub async fn get_withdrawable_stake(&self) -> Result<WithdrawableStakeResponse, BeeDebugStakeError> {
+        let url = self.base_url.join("stake/withdrawable")?;
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.json().await?)
+    }
+
+    /// Withdraw the stake exceeding what the storage radius of the node requires.
+    pub async fn withdraw_surplus_stake(&self, gas_price: Option<&str>, gas_limit: Option<&str>) -> Result<TransactionHash, BeeDebugStakeError> {
+        let url = self.base_url.join("stake/withdrawable")?;
+        let request = with_gas_options(self.client.delete(url), gas_price, gas_limit)?;
+        send_transaction(request).await
+    }
+
+    /// Withdraw the whole stake from a paused staking contract, to migrate it
+    /// to a new one.
+    pub async fn migrate_stake(&self, gas_price: Option<&str>, gas_limit: Option<&str>) -> Result<TransactionHash, BeeDebugStakeError> {
+        let url = self.base_url.join("stake")?;
+        let request = with_gas_options(self.client.delete(url), gas_price, gas_limit)?;
+        send_transaction(request).await
+    }
+
+    pub async fn get_redistribution_state(&self) -> Result<RedistributionState, BeeDebugStakeError> {
+        let url = self.base_url.join("redistributionstate")?;
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.json().await?)
+    }
+}
+
+fn with_gas_options(request: RequestBuilder, gas_price: Option<&str>, gas_limit: Option<&str>) -> Result<RequestBuilder, BeeDebugStakeError> {
+    let mut headers = HeaderMap::new();
+    if let Some(gp) = gas_price {
+        headers.insert(HeaderName::from_static("gas-price"), HeaderValue::from_str(gp).map_err(|e| BeeDebugStakeError::Header(format!("Invalid gas-price header: {}", e)))?);
+    }
+    if let Some(gl) = gas_limit {
+        headers.insert(HeaderName::from_static("gas-limit"), HeaderValue::from_str(gl).map_err(|e| BeeDebugStakeError::Header(format!("Invalid gas-limit header: {}", e)))?);
+    }
+    Ok(request.headers(headers))
+}
+
+async fn send_transaction(request: RequestBuilder) -> Result<TransactionHash, BeeDebugStakeError> {
+    let response = request.send().await?.error_for_status()?;
+    let transaction: TransactionResponse = response.json().await?;
+    Ok(transaction.tx_hash)
+}
@@ -5,6 +5,11 @@ use thiserror::Error;
 use std::collections::HashMap;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde_json::Value;
+use std::time::Duration;
+
+use crate::api::typed_bytes::{BatchId, TransactionHash};
+use crate::utils::stamps;
+use crate::utils::tokens::BZZ;
 
 #[derive(Error, Debug)]
 pub enum BeeDebugStampsError {
@@ -16,41 +21,107 @@ pub enum BeeDebugStampsError {
     Deserialization(String),
     #[error("Header error: {0}")]
     Header(String),
+    #[error("Batch {0} not usable after {1:?}")]
+    NotUsable(String, Duration),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GlobalPostageBatch {
-    pub batch_id: String,
-    pub value: String,
-    pub start: u32,
-    pub depth: u32,
-    pub bucket_depth: u32,
+    #[serde(rename = "batchID")]
+    pub batch_id: BatchId,
+    /// The total amount in PLUR per chunk paid for the batch, including
+    /// what has already been used up.
+    pub value: BZZ,
+    pub start: u64,
+    pub depth: u8,
+    pub bucket_depth: u8,
+    #[serde(rename = "immutable", alias = "immutableFlag")]
     pub immutable_flag: bool,
-    pub batch_ttl: u32,
+    /// The time to live in seconds, or -1 if unknown.
+    #[serde(rename = "batchTTL")]
+    pub batch_ttl: i64,
     pub owner: String,
-    pub storage_radius: u32,
+    /// Only reported by Bee 1.x.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_radius: Option<u8>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PostageBatch {
-    pub batch_id: String,
+    #[serde(rename = "batchID")]
+    pub batch_id: BatchId,
     pub utilization: u32,
     pub usable: bool,
-    pub label: String,
-    pub depth: u32,
-    pub amount: String,
-    pub bucket_depth: u32,
-    pub block_number: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub depth: u8,
+    /// The amount in PLUR per chunk paid for the batch.
+    pub amount: BZZ,
+    pub bucket_depth: u8,
+    pub block_number: u64,
     pub immutable_flag: bool,
     pub exists: bool,
-    pub batch_ttl: u32,
+    /// The time to live in seconds, or -1 if unknown.
+    #[serde(rename = "batchTTL")]
+    pub batch_ttl: i64,
+}
+
+impl PostageBatch {
+    /// The time to live of the batch, if known to the node.
+    pub fn ttl(&self) -> Option<Duration> {
+        u64::try_from(self.batch_ttl).ok().map(Duration::from_secs)
+    }
+
+    /// The fill of the batch in percent, as the share of its fullest bucket
+    /// that is in use.
+    pub fn utilization_percent(&self) -> f64 {
+        stamps::utilization_percent(self.utilization, self.depth, self.bucket_depth)
+    }
+
+    /// The number of bytes that can still be stamped before the fullest
+    /// bucket of the batch overflows, assuming the other buckets fill up at
+    /// the same rate.
+    ///
+    /// Returns 0 for batches reported shallower than their buckets.
+    pub fn remaining_capacity(&self) -> u64 {
+        let Some(bucket_capacity) = self
+            .depth
+            .checked_sub(self.bucket_depth)
+            .and_then(|slot_depth| 1u64.checked_shl(slot_depth.into()))
+        else {
+            return 0;
+        };
+        let free_slots = bucket_capacity.saturating_sub(self.utilization.into());
+        stamps::theoretical_volume(self.bucket_depth).map_or(u64::MAX, |volume| volume.saturating_mul(free_slots))
+    }
+}
+
+/// The criteria of [`BeeDebugStampsClient::find_batch`].
+#[derive(Debug, Clone, Default)]
+pub struct BatchFilter {
+    /// The label of the batch.
+    pub label: Option<String>,
+    /// The minimum time to live of the batch.
+    pub min_ttl: Option<Duration>,
+    /// The minimum number of bytes that can still be stamped, see
+    /// [`PostageBatch::remaining_capacity`].
+    pub min_free_capacity: Option<u64>,
+}
+
+impl BatchFilter {
+    fn matches(&self, batch: &PostageBatch) -> bool {
+        self.label.as_ref().is_none_or(|label| batch.label.as_ref() == Some(label))
+            && self.min_ttl.is_none_or(|min_ttl| batch.ttl().is_some_and(|ttl| ttl >= min_ttl))
+            && self.min_free_capacity.is_none_or(|capacity| batch.remaining_capacity() >= capacity)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PostageBatchBucket {
+    #[serde(rename = "bucketID")]
     pub bucket_id: u32,
     pub collisions: u32,
 }
@@ -58,12 +129,23 @@ pub struct PostageBatchBucket {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PostageBatchBuckets {
-    pub depth: u32,
-    pub bucket_depth: u32,
+    pub depth: u8,
+    pub bucket_depth: u8,
     pub bucket_upper_bound: u32,
     pub buckets: Vec<PostageBatchBucket>,
 }
 
+/// The response of Bee to creating, topping up or diluting a batch.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchTransaction {
+    #[serde(rename = "batchID")]
+    batch_id: BatchId,
+    tx_hash: TransactionHash,
+}
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct BeeDebugStampsClient {
     client: Client,
     base_url: Url,
@@ -106,8 +188,50 @@ impl BeeDebugStampsClient {
         Ok(response.json().await?)
     }
 
-    pub async fn create_postage_batch(&self, amount: &str, depth: u32, gas_price: Option<&str>, immutable_flag: Option<bool>, label: Option<&str>) -> Result<String, BeeDebugStampsError> {
-        let url = self.base_url.join(&format!("stamps/{}/{}", amount, depth))?;
+    /// Find the usable batch matching `filter` with the longest time to live.
+    pub async fn find_batch(&self, filter: &BatchFilter) -> Result<Option<PostageBatch>, BeeDebugStampsError> {
+        let batches = self.get_all_postage_batches().await?;
+        Ok(batches
+            .into_iter()
+            .filter(|batch| batch.usable && filter.matches(batch))
+            .max_by_key(|batch| batch.batch_ttl))
+    }
+
+    /// Poll a batch until it can be used to stamp chunks.
+    ///
+    /// A new batch is unknown to the node until the transaction creating it
+    /// is mined, and not usable until a few blocks later.
+    pub async fn wait_until_usable(&self, batch_id: &str, timeout: Duration) -> Result<PostageBatch, BeeDebugStampsError> {
+        self.poll_until_usable(batch_id, timeout, POLL_INTERVAL, |_| true).await
+    }
+
+    /// Like [`BeeDebugStampsClient::wait_until_usable`], polling every
+    /// `poll_interval` until the usable batch also satisfies `ready`, e.g.
+    /// until the node reports a dilute or top-up.
+    pub(crate) async fn poll_until_usable(
+        &self,
+        batch_id: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+        ready: impl Fn(&PostageBatch) -> bool,
+    ) -> Result<PostageBatch, BeeDebugStampsError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            match self.get_postage_batch(batch_id).await {
+                Ok(batch) if batch.exists && batch.usable && ready(&batch) => return Ok(batch),
+                Ok(_) => {}
+                Err(BeeDebugStampsError::Reqwest(e)) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => {}
+                Err(e) => return Err(e),
+            }
+            if tokio::time::Instant::now() + poll_interval > deadline {
+                return Err(BeeDebugStampsError::NotUsable(batch_id.to_string(), timeout));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    pub async fn create_postage_batch(&self, amount: &BZZ, depth: u8, gas_price: Option<&str>, immutable_flag: Option<bool>, label: Option<&str>) -> Result<BatchId, BeeDebugStampsError> {
+        let url = self.base_url.join(&format!("stamps/{}/{}", amount.to_plur(), depth))?;
         let mut request = self.client.post(url);
 
         let mut headers = HashMap::new();
@@ -130,21 +254,21 @@ impl BeeDebugStampsClient {
         }
 
         let response = request.send().await?.error_for_status()?;
-        let body: serde_json::Value = response.json().await?;
-        Ok(body["batchID"].as_str().unwrap_or_default().to_string())
+        let transaction: BatchTransaction = response.json().await?;
+        Ok(transaction.batch_id)
     }
 
-    pub async fn top_up_batch(&self, id: &str, amount: &str) -> Result<String, BeeDebugStampsError> {
-        let url = self.base_url.join(&format!("stamps/topup/{}/{}", id, amount))?;
+    pub async fn top_up_batch(&self, id: &str, amount: &BZZ) -> Result<TransactionHash, BeeDebugStampsError> {
+        let url = self.base_url.join(&format!("stamps/topup/{}/{}", id, amount.to_plur()))?;
         let response = self.client.patch(url).send().await?.error_for_status()?;
-        let body: serde_json::Value = response.json().await?;
-        Ok(body["batchID"].as_str().unwrap_or_default().to_string())
+        let transaction: BatchTransaction = response.json().await?;
+        Ok(transaction.tx_hash)
     }
 
-    pub async fn dilute_batch(&self, id: &str, depth: u32) -> Result<String, BeeDebugStampsError> {
+    pub async fn dilute_batch(&self, id: &str, depth: u8) -> Result<TransactionHash, BeeDebugStampsError> {
         let url = self.base_url.join(&format!("stamps/dilute/{}/{}", id, depth))?;
         let response = self.client.patch(url).send().await?.error_for_status()?;
-        let body: serde_json::Value = response.json().await?;
-        Ok(body["batchID"].as_str().unwrap_or_default().to_string())
+        let transaction: BatchTransaction = response.json().await?;
+        Ok(transaction.tx_hash)
     }
 }
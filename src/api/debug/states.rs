@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use url::{Url, ParseError as UrlParseError};
 use thiserror::Error;
 
+use crate::utils::tokens::{BZZ, DAI};
+
 #[derive(Error, Debug)]
 pub enum BeeDebugStatesError {
     #[error("Request error: {0}")]
@@ -30,15 +32,16 @@ pub struct ReserveState {
 pub struct ChainState {
     pub block: u64,
     pub chain_tip: u64,
-    pub total_amount: String,
-    pub current_price: String,
+    pub total_amount: BZZ,
+    /// The storage price in PLUR per chunk and block.
+    pub current_price: BZZ,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WalletBalance {
-    pub bzz_balance: String,
-    pub native_token_balance: String,
+    pub bzz_balance: BZZ,
+    pub native_token_balance: DAI,
 }
 
 pub struct BeeDebugStatesClient {
@@ -1,18 +1,79 @@
 use reqwest::{Client, Error};
 use serde::{Deserialize, Serialize};
 
+use crate::api::signer::ADDRESS_SIZE;
+use crate::api::stamper::Stamp;
+use crate::error::Error as BeeError;
+
 const ENVELOPE_ENDPOINT: &str = "envelope";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EnvelopeWithBatchId {
+    #[serde(with = "hex_bytes")]
     pub issuer: Vec<u8>,
+    #[serde(with = "hex_bytes")]
     pub index: Vec<u8>,
+    #[serde(with = "hex_bytes")]
     pub timestamp: Vec<u8>,
+    #[serde(with = "hex_bytes")]
     pub signature: Vec<u8>,
-    #[serde(rename = "batchId")]
+    #[serde(rename = "batchId", default)]
     pub batch_id: String,
 }
 
+impl EnvelopeWithBatchId {
+    /// The postage stamp contained in the envelope.
+    pub fn stamp(&self) -> Result<Stamp, BeeError> {
+        let batch_id = hex::decode(&self.batch_id)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| BeeError::Custom(format!("invalid batch ID: {}", self.batch_id)))?;
+        let index = self.index.as_slice().try_into().map_err(|_| {
+            BeeError::Custom(format!("invalid envelope index length: {}", self.index.len()))
+        })?;
+        let timestamp = self.timestamp.as_slice().try_into().map_err(|_| {
+            BeeError::Custom(format!("invalid envelope timestamp length: {}", self.timestamp.len()))
+        })?;
+        let signature = self.signature.as_slice().try_into().map_err(|_| {
+            BeeError::Custom(format!("invalid envelope signature length: {}", self.signature.len()))
+        })?;
+
+        Ok(Stamp {
+            batch_id,
+            index,
+            timestamp: u64::from_be_bytes(timestamp),
+            signature,
+        })
+    }
+
+    /// Verify offline that the envelope was signed by its issuer, the owner of
+    /// the batch, for the chunk with the given hex encoded address.
+    pub fn verify(&self, reference: &str) -> Result<(), BeeError> {
+        let chunk_address: [u8; 32] = hex::decode(reference)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| BeeError::Custom(format!("invalid chunk address: {}", reference)))?;
+        let issuer: [u8; ADDRESS_SIZE] = self.issuer.as_slice().try_into().map_err(|_| {
+            BeeError::Custom(format!("invalid envelope issuer length: {}", self.issuer.len()))
+        })?;
+        self.stamp()?.verify(&chunk_address, &issuer)
+    }
+}
+
+/// Bee encodes the binary fields of an envelope as hex strings.
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        hex::decode(value.strip_prefix("0x").unwrap_or(&value)).map_err(serde::de::Error::custom)
+    }
+}
+
 pub async fn post_envelope(
     client: &Client,
     base_url: &str,
@@ -0,0 +1,114 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Request and response headers shared by the upload endpoints.
+//! https://github.com/ethersphere/bee-js/blob/main/src/modules/headers.ts
+
+use reqwest::{RequestBuilder, Response};
+
+use crate::api::bytes::{DownloadOptions, RedundantUploadOptions, UploadOptions, UploadResult};
+use crate::api::stamper::Stamp;
+
+/// How the chunks of an upload are stamped.
+pub(crate) enum Postage<'a> {
+    /// Stamped by the node with a batch it owns.
+    BatchId(&'a str),
+    /// Stamped in advance by the batch owner.
+    Stamp(&'a Stamp),
+}
+
+/// Add the postage header of an upload to a request.
+pub(crate) fn with_postage(request_builder: RequestBuilder, postage: Postage) -> RequestBuilder {
+    match postage {
+        Postage::BatchId(batch_id) => request_builder.header("swarm-postage-batch-id", batch_id),
+        Postage::Stamp(stamp) => request_builder.header("swarm-postage-stamp", hex::encode(stamp.to_bytes())),
+    }
+}
+
+/// Add the `swarm-*` headers of the upload options to a request.
+pub(crate) fn with_upload_options(
+    mut request_builder: RequestBuilder,
+    options: &UploadOptions,
+) -> RequestBuilder {
+    if let Some(act) = options.act {
+        request_builder = request_builder.header("swarm-act", act.to_string());
+    }
+    if let Some(act_history_address) = &options.act_history_address {
+        request_builder = request_builder.header("swarm-act-history-address", act_history_address);
+    }
+    if let Some(pin) = options.pin {
+        request_builder = request_builder.header("swarm-pin", pin.to_string());
+    }
+    if let Some(encrypt) = options.encrypt {
+        request_builder = request_builder.header("swarm-encrypt", encrypt.to_string());
+    }
+    if let Some(tag) = options.tag {
+        request_builder = request_builder.header("swarm-tag", tag.to_string());
+    }
+    if let Some(deferred) = options.deferred {
+        request_builder = request_builder.header("swarm-deferred", deferred.to_string());
+    }
+    request_builder
+}
+
+/// Add the `swarm-*` headers of the redundant upload options to a request.
+pub(crate) fn with_redundant_upload_options(
+    request_builder: RequestBuilder,
+    options: &RedundantUploadOptions,
+) -> RequestBuilder {
+    let mut request_builder = with_upload_options(request_builder, &options.upload_options);
+    if let Some(redundancy_level) = options.redundancy_level {
+        request_builder = request_builder.header("swarm-redundancy-level", redundancy_level.to_string());
+    }
+    request_builder
+}
+
+/// Add the `swarm-*` headers of the download options to a request.
+pub(crate) fn with_download_options(
+    mut request_builder: RequestBuilder,
+    options: &DownloadOptions,
+) -> RequestBuilder {
+    if let Some(redundancy_strategy) = options.redundancy_strategy {
+        request_builder = request_builder.header("swarm-redundancy-strategy", redundancy_strategy.to_string());
+    }
+    if let Some(fallback) = options.fallback {
+        request_builder = request_builder.header("swarm-fallback", fallback.to_string());
+    }
+    if let Some(timeout_ms) = options.timeout_ms {
+        request_builder = request_builder.header("swarm-timeout", timeout_ms.to_string());
+    }
+    if let Some(act_publisher) = &options.act_publisher {
+        request_builder = request_builder.header("swarm-act-publisher", act_publisher);
+    }
+    if let Some(act_history_address) = &options.act_history_address {
+        request_builder = request_builder.header("swarm-act-history-address", act_history_address);
+    }
+    if let Some(act_timestamp) = options.act_timestamp {
+        request_builder = request_builder.header("swarm-act-timestamp", act_timestamp.to_string());
+    }
+    request_builder
+}
+
+/// Read the upload result from the body and the `swarm-tag` and
+/// `swarm-act-history-address` headers of a response.
+pub(crate) async fn read_upload_result(response: Response) -> Result<UploadResult, reqwest::Error> {
+    let response = response.error_for_status()?;
+
+    let tag_uid = response
+        .headers()
+        .get("swarm-tag")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|s| s.parse::<u32>().ok());
+
+    let history_address = response
+        .headers()
+        .get("swarm-act-history-address")
+        .and_then(|value| value.to_str().ok())
+        .map(|s| s.to_string());
+
+    let mut upload_result: UploadResult = response.json().await?;
+    upload_result.tag_uid = tag_uid;
+    upload_result.history_address = history_address;
+
+    Ok(upload_result)
+}
@@ -0,0 +1,324 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Editing of collection manifests.
+//! https://github.com/ethersphere/bee-js/blob/main/src/manifest/manifest.ts
+//!
+//! A [`Manifest`] loads Mantaray nodes lazily along the paths that are
+//! touched, so editing a single file of a large collection only downloads and
+//! re-uploads the nodes between the root and that file.
+
+use std::collections::BTreeMap;
+
+use futures::future::BoxFuture;
+use futures::{StreamExt, TryStreamExt};
+use reqwest::{Client, StatusCode};
+
+use crate::api::bytes::{self, RedundantUploadOptions};
+use crate::api::mantaray::MantarayNode;
+use crate::error::Error;
+
+/// Metadata key holding the content type of an entry.
+pub const CONTENT_TYPE_KEY: &str = "Content-Type";
+/// Metadata key holding the file name of an entry.
+pub const FILENAME_KEY: &str = "Filename";
+/// Metadata key of the root path holding the index document.
+pub const INDEX_DOCUMENT_KEY: &str = "website-index-document";
+/// Metadata key of the root path holding the error document.
+pub const ERROR_DOCUMENT_KEY: &str = "website-error-document";
+
+const ROOT_PATH: &str = "/";
+const LOAD_CONCURRENCY: usize = 8;
+
+/// A file entry of a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub reference: String,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// A collection manifest that can be edited and saved back to Swarm.
+pub struct Manifest {
+    client: Client,
+    base_url: String,
+    root: MantarayNode,
+}
+
+impl Manifest {
+    /// Create a new, empty manifest.
+    pub fn new(client: &Client, base_url: &str) -> Self {
+        Self {
+            client: client.clone(),
+            base_url: base_url.to_string(),
+            root: MantarayNode::new(),
+        }
+    }
+
+    /// Load the manifest stored under `reference`.
+    ///
+    /// Only the root node is downloaded, other nodes are fetched on demand.
+    pub async fn load(client: &Client, base_url: &str, reference: &str) -> Result<Self, Error> {
+        let address = decode_reference(reference)?;
+        let data = download_node(client, base_url, reference).await?;
+        let mut root = MantarayNode::unmarshal(&data)?;
+        root.set_address(address);
+
+        Ok(Self {
+            client: client.clone(),
+            base_url: base_url.to_string(),
+            root,
+        })
+    }
+
+    /// The root node of the manifest.
+    pub fn root(&self) -> &MantarayNode {
+        &self.root
+    }
+
+    /// The reference of the manifest, unless it has unsaved changes.
+    pub fn reference(&self) -> Option<String> {
+        self.root.address().map(hex::encode)
+    }
+
+    /// Add a file entry, replacing any entry already stored at `path`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the file inside the collection.
+    /// * `reference` - The reference of the file content.
+    /// * `metadata` - Metadata such as [`CONTENT_TYPE_KEY`] and [`FILENAME_KEY`].
+    pub async fn add_file(
+        &mut self,
+        path: &str,
+        reference: &str,
+        metadata: BTreeMap<String, String>,
+    ) -> Result<(), Error> {
+        let path = normalize_path(path)?;
+        let entry = decode_reference(reference)?;
+        self.load_path(path.as_bytes()).await?;
+        self.root.add(path.as_bytes(), &entry, metadata)
+    }
+
+    /// Remove the entry stored at `path`.
+    pub async fn remove(&mut self, path: &str) -> Result<(), Error> {
+        let path = normalize_path(path)?;
+        self.load_path(path.as_bytes()).await?;
+        self.root.remove(path.as_bytes())
+    }
+
+    /// Move the entry stored at `from` to `to`, keeping its reference and
+    /// metadata. The file name metadata is updated to the new name.
+    pub async fn rename(&mut self, from: &str, to: &str) -> Result<(), Error> {
+        let from = normalize_path(from)?;
+        let to = normalize_path(to)?;
+        self.load_path(from.as_bytes()).await?;
+
+        let node = self
+            .root
+            .find(from.as_bytes())
+            .filter(|node| node.is_value())
+            .ok_or_else(|| Error::Custom(format!("path not found in manifest: {}", from)))?;
+        let entry = node.entry().to_vec();
+        let mut metadata = node.metadata().cloned().unwrap_or_default();
+        if metadata.contains_key(FILENAME_KEY) {
+            let name = to.rsplit('/').next().unwrap_or(&to);
+            metadata.insert(FILENAME_KEY.to_string(), name.to_string());
+        }
+
+        self.root.remove(from.as_bytes())?;
+        self.load_path(to.as_bytes()).await?;
+        self.root.add(to.as_bytes(), &entry, metadata)
+    }
+
+    /// Download every node that has not been loaded yet.
+    pub async fn load_all(&mut self) -> Result<(), Error> {
+        load_subtree(&self.client, &self.base_url, &mut self.root).await
+    }
+
+    /// Walk the whole manifest and return its file entries ordered by path.
+    ///
+    /// The root metadata entry holding the index and error documents is not
+    /// included.
+    pub async fn entries(&mut self) -> Result<Vec<ManifestEntry>, Error> {
+        self.load_all().await?;
+        let mut entries = Vec::new();
+        collect_entries(&self.root, &mut Vec::new(), &mut entries);
+        Ok(entries)
+    }
+
+    /// The index document of the collection, if any.
+    pub fn index_document(&self) -> Option<&str> {
+        self.root_metadata(INDEX_DOCUMENT_KEY)
+    }
+
+    /// The error document of the collection, if any.
+    pub fn error_document(&self) -> Option<&str> {
+        self.root_metadata(ERROR_DOCUMENT_KEY)
+    }
+
+    /// Set the document served for the root of the collection.
+    pub async fn set_index_document(&mut self, path: &str) -> Result<(), Error> {
+        self.set_root_metadata(INDEX_DOCUMENT_KEY, path).await
+    }
+
+    /// Set the document served when a path is not found.
+    pub async fn set_error_document(&mut self, path: &str) -> Result<(), Error> {
+        self.set_root_metadata(ERROR_DOCUMENT_KEY, path).await
+    }
+
+    /// Upload every changed node and return the new reference of the
+    /// manifest. Unchanged nodes are not uploaded again.
+    pub async fn save(
+        &mut self,
+        postage_batch_id: &str,
+        options: Option<RedundantUploadOptions>,
+    ) -> Result<String, Error> {
+        let address = save_node(
+            &self.client,
+            &self.base_url,
+            &mut self.root,
+            postage_batch_id,
+            &options,
+        )
+        .await?;
+        Ok(hex::encode(address))
+    }
+
+    fn root_metadata(&self, key: &str) -> Option<&str> {
+        self.root
+            .find(ROOT_PATH.as_bytes())
+            .and_then(|node| node.metadata())
+            .and_then(|metadata| metadata.get(key))
+            .map(String::as_str)
+    }
+
+    async fn set_root_metadata(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        self.load_path(ROOT_PATH.as_bytes()).await?;
+        let mut metadata = self
+            .root
+            .find(ROOT_PATH.as_bytes())
+            .and_then(|node| node.metadata())
+            .cloned()
+            .unwrap_or_default();
+        metadata.insert(key.to_string(), value.to_string());
+        let entry = vec![0u8; self.root.ref_bytes_size()];
+        self.root.add(ROOT_PATH.as_bytes(), &entry, metadata)
+    }
+
+    /// Download the nodes along `path` that are needed to modify it.
+    async fn load_path(&mut self, path: &[u8]) -> Result<(), Error> {
+        let mut node = &mut self.root;
+        let mut rest = path;
+
+        while !rest.is_empty() {
+            let Some(fork) = node.forks_mut().get_mut(&rest[0]) else {
+                break;
+            };
+            if !rest.starts_with(&fork.prefix) {
+                break;
+            }
+            load_node(&self.client, &self.base_url, &mut fork.node).await?;
+            rest = &rest[fork.prefix.len()..];
+            node = &mut fork.node;
+        }
+
+        Ok(())
+    }
+}
+
+async fn load_node(client: &Client, base_url: &str, node: &mut MantarayNode) -> Result<(), Error> {
+    if node.is_loaded() {
+        return Ok(());
+    }
+    let reference = hex::encode(node.address().unwrap_or_default());
+    let data = download_node(client, base_url, &reference).await?;
+    node.load(&data)
+}
+
+/// Download the data of a node, failing on error responses instead of
+/// parsing their body as a node.
+async fn download_node(client: &Client, base_url: &str, reference: &str) -> Result<Vec<u8>, Error> {
+    let url = format!("{}/bytes/{}", base_url, reference);
+    let response = client.get(&url).send().await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(Error::NotFound(reference.to_string()));
+    }
+    let data = response.error_for_status()?.bytes().await?;
+    Ok(data.to_vec())
+}
+
+fn load_subtree<'a>(
+    client: &'a Client,
+    base_url: &'a str,
+    node: &'a mut MantarayNode,
+) -> BoxFuture<'a, Result<(), Error>> {
+    Box::pin(async move {
+        load_node(client, base_url, node).await?;
+        let loads: Vec<_> = node
+            .forks_mut()
+            .values_mut()
+            .map(|fork| load_subtree(client, base_url, &mut fork.node))
+            .collect();
+        futures::stream::iter(loads)
+            .buffer_unordered(LOAD_CONCURRENCY)
+            .try_collect::<()>()
+            .await
+    })
+}
+
+fn collect_entries(node: &MantarayNode, path: &mut Vec<u8>, entries: &mut Vec<ManifestEntry>) {
+    if node.is_value() && path.as_slice() != ROOT_PATH.as_bytes() {
+        entries.push(ManifestEntry {
+            path: String::from_utf8_lossy(path).into_owned(),
+            reference: hex::encode(node.entry()),
+            metadata: node.metadata().cloned().unwrap_or_default(),
+        });
+    }
+    for fork in node.forks().values() {
+        path.extend_from_slice(&fork.prefix);
+        collect_entries(&fork.node, path, entries);
+        path.truncate(path.len() - fork.prefix.len());
+    }
+}
+
+fn save_node<'a>(
+    client: &'a Client,
+    base_url: &'a str,
+    node: &'a mut MantarayNode,
+    postage_batch_id: &'a str,
+    options: &'a Option<RedundantUploadOptions>,
+) -> BoxFuture<'a, Result<Vec<u8>, Error>> {
+    Box::pin(async move {
+        if let Some(address) = node.address() {
+            return Ok(address.to_vec());
+        }
+
+        for fork in node.forks_mut().values_mut() {
+            save_node(client, base_url, &mut fork.node, postage_batch_id, options).await?;
+        }
+
+        let data = node.marshal()?;
+        let result = bytes::upload(client, base_url, data, postage_batch_id, options.clone()).await?;
+        let address = decode_reference(&result.reference)?;
+        node.set_address(address.clone());
+        Ok(address)
+    })
+}
+
+fn normalize_path(path: &str) -> Result<String, Error> {
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        return Err(Error::Custom("path cannot be empty".to_string()));
+    }
+    Ok(path.to_string())
+}
+
+fn decode_reference(reference: &str) -> Result<Vec<u8>, Error> {
+    let address = hex::decode(reference)
+        .map_err(|e| Error::Custom(format!("invalid reference {}: {}", reference, e)))?;
+    if address.len() != 32 && address.len() != 64 {
+        return Err(Error::Custom(format!("invalid reference length: {}", reference)));
+    }
+    Ok(address)
+}
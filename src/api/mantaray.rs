@@ -0,0 +1,504 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mantaray manifest node implementation.
+//! https://github.com/ethersphere/bee/blob/master/pkg/manifest/mantaray/marshal.go
+//!
+//! A Mantaray manifest is a compacted trie of paths. Every node is stored in
+//! Swarm as a separate `/bytes` upload and only references its children by
+//! address, so a node that has not been loaded yet is represented by its
+//! address alone.
+
+use std::collections::BTreeMap;
+
+use hex_literal::hex;
+
+use crate::error::Error;
+
+/// First 31 bytes of `keccak256("mantaray:0.2")`.
+pub const VERSION_02_HASH: [u8; 31] =
+    hex!("5768b3b6a7db56d21d1abff40d41cebfc83448fed8d7e9b06ec0d3b073f28f");
+
+/// The path separator used by collections.
+pub const PATH_SEPARATOR: u8 = b'/';
+
+const OBFUSCATION_KEY_SIZE: usize = 32;
+const NODE_HEADER_SIZE: usize = 64;
+const FORK_PRE_REFERENCE_SIZE: usize = 32;
+const FORK_PREFIX_MAX_SIZE: usize = 30;
+const FORK_METADATA_BYTES_SIZE: usize = 2;
+const DEFAULT_REF_BYTES_SIZE: usize = 32;
+
+const TYPE_VALUE: u8 = 2;
+const TYPE_EDGE: u8 = 4;
+const TYPE_WITH_PATH_SEPARATOR: u8 = 8;
+const TYPE_WITH_METADATA: u8 = 16;
+
+/// A fork of a node: the path segment leading to a child node.
+#[derive(Debug, Clone)]
+pub struct Fork {
+    pub prefix: Vec<u8>,
+    pub node: MantarayNode,
+}
+
+/// A node of a Mantaray manifest.
+#[derive(Debug, Clone)]
+pub struct MantarayNode {
+    obfuscation_key: [u8; OBFUSCATION_KEY_SIZE],
+    node_type: u8,
+    ref_bytes_size: usize,
+    entry: Vec<u8>,
+    metadata: Option<BTreeMap<String, String>>,
+    forks: BTreeMap<u8, Fork>,
+    address: Option<Vec<u8>>,
+    loaded: bool,
+}
+
+impl Default for MantarayNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MantarayNode {
+    /// Create a new, empty node.
+    pub fn new() -> Self {
+        Self {
+            obfuscation_key: [0u8; OBFUSCATION_KEY_SIZE],
+            node_type: 0,
+            ref_bytes_size: DEFAULT_REF_BYTES_SIZE,
+            entry: Vec::new(),
+            metadata: None,
+            forks: BTreeMap::new(),
+            address: None,
+            loaded: true,
+        }
+    }
+
+    /// Create a node that is only known by its address.
+    ///
+    /// The content has to be provided with [`MantarayNode::load`] before the
+    /// node can be read or modified.
+    pub fn from_address(address: Vec<u8>) -> Self {
+        Self {
+            ref_bytes_size: address.len(),
+            address: Some(address),
+            loaded: false,
+            ..Self::new()
+        }
+    }
+
+    /// Deserialize a node from its binary representation.
+    pub fn unmarshal(data: &[u8]) -> Result<Self, Error> {
+        let mut node = Self::new();
+        node.loaded = false;
+        node.load(data)?;
+        if !node.forks.is_empty() {
+            node.node_type |= TYPE_EDGE;
+        }
+        Ok(node)
+    }
+
+    /// Whether the content of the node is available.
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// The Swarm address of the node, if it has not changed since it was
+    /// loaded or saved.
+    pub fn address(&self) -> Option<&[u8]> {
+        self.address.as_deref()
+    }
+
+    /// The reference stored in the node. Only meaningful for value nodes.
+    pub fn entry(&self) -> &[u8] {
+        &self.entry
+    }
+
+    /// The metadata attached to the node.
+    pub fn metadata(&self) -> Option<&BTreeMap<String, String>> {
+        self.metadata.as_ref()
+    }
+
+    /// The forks of the node, keyed by the first byte of their prefix.
+    pub fn forks(&self) -> &BTreeMap<u8, Fork> {
+        &self.forks
+    }
+
+    pub(crate) fn forks_mut(&mut self) -> &mut BTreeMap<u8, Fork> {
+        &mut self.forks
+    }
+
+    /// Whether the node holds an entry.
+    pub fn is_value(&self) -> bool {
+        self.node_type & TYPE_VALUE != 0
+    }
+
+    /// Whether the node has forks.
+    pub fn is_edge(&self) -> bool {
+        self.node_type & TYPE_EDGE != 0
+    }
+
+    /// The size of the references used by the node.
+    pub fn ref_bytes_size(&self) -> usize {
+        self.ref_bytes_size
+    }
+
+    /// Record the Swarm address the node was saved under.
+    pub(crate) fn set_address(&mut self, address: Vec<u8>) {
+        self.address = Some(address);
+    }
+
+    /// Fill a node created with [`MantarayNode::from_address`] with its
+    /// content. Node type and metadata are kept, as they are stored in the
+    /// parent fork rather than in the node itself.
+    pub fn load(&mut self, data: &[u8]) -> Result<(), Error> {
+        if data.len() < NODE_HEADER_SIZE {
+            return Err(Error::Custom(format!(
+                "mantaray node too short: {} bytes",
+                data.len()
+            )));
+        }
+
+        let mut obfuscation_key = [0u8; OBFUSCATION_KEY_SIZE];
+        obfuscation_key.copy_from_slice(&data[..OBFUSCATION_KEY_SIZE]);
+        let data = xor_with_key(data, &obfuscation_key);
+
+        if data[OBFUSCATION_KEY_SIZE..NODE_HEADER_SIZE - 1] != VERSION_02_HASH {
+            return Err(Error::Custom(
+                "unsupported mantaray version".to_string(),
+            ));
+        }
+
+        let ref_bytes_size = data[NODE_HEADER_SIZE - 1] as usize;
+        let mut offset = NODE_HEADER_SIZE;
+        let entry = read(&data, offset, ref_bytes_size)?.to_vec();
+        offset += ref_bytes_size;
+        let index = read(&data, offset, 32)?.to_vec();
+        offset += 32;
+
+        let mut forks = BTreeMap::new();
+        for byte in 0..=255u8 {
+            if index[byte as usize / 8] & (1 << (byte % 8)) == 0 {
+                continue;
+            }
+
+            let header = read(&data, offset, FORK_PRE_REFERENCE_SIZE + ref_bytes_size)?;
+            let node_type = header[0];
+            let prefix_length = header[1] as usize;
+            if prefix_length == 0 || prefix_length > FORK_PREFIX_MAX_SIZE {
+                return Err(Error::Custom(format!(
+                    "invalid mantaray fork prefix length {}",
+                    prefix_length
+                )));
+            }
+            let prefix = header[2..2 + prefix_length].to_vec();
+            let address = header[FORK_PRE_REFERENCE_SIZE..].to_vec();
+            offset += FORK_PRE_REFERENCE_SIZE + ref_bytes_size;
+
+            let mut node = MantarayNode::from_address(address);
+            node.obfuscation_key = obfuscation_key;
+            node.node_type = node_type;
+
+            if node_type & TYPE_WITH_METADATA != 0 {
+                let size_bytes = read(&data, offset, FORK_METADATA_BYTES_SIZE)?;
+                let size = u16::from_be_bytes([size_bytes[0], size_bytes[1]]) as usize;
+                offset += FORK_METADATA_BYTES_SIZE;
+                let metadata = serde_json::from_slice(read(&data, offset, size)?)
+                    .map_err(|e| Error::Custom(format!("invalid mantaray metadata: {}", e)))?;
+                node.metadata = Some(metadata);
+                offset += size;
+            }
+
+            forks.insert(byte, Fork { prefix, node });
+        }
+
+        self.obfuscation_key = obfuscation_key;
+        self.ref_bytes_size = ref_bytes_size;
+        self.entry = entry;
+        self.forks = forks;
+        self.loaded = true;
+        Ok(())
+    }
+
+    /// Serialize the node into its binary representation.
+    ///
+    /// All forks must have an address, i.e. changed child nodes have to be
+    /// saved first.
+    pub fn marshal(&self) -> Result<Vec<u8>, Error> {
+        if !self.loaded {
+            return Err(Error::Custom("mantaray node is not loaded".to_string()));
+        }
+
+        let mut data = Vec::with_capacity(NODE_HEADER_SIZE + self.ref_bytes_size + 32);
+        data.extend_from_slice(&self.obfuscation_key);
+        data.extend_from_slice(&VERSION_02_HASH);
+        data.push(self.ref_bytes_size as u8);
+
+        if self.entry.is_empty() {
+            data.resize(data.len() + self.ref_bytes_size, 0);
+        } else if self.entry.len() == self.ref_bytes_size {
+            data.extend_from_slice(&self.entry);
+        } else {
+            return Err(Error::Custom(format!(
+                "entry size {} does not match reference size {}",
+                self.entry.len(),
+                self.ref_bytes_size
+            )));
+        }
+
+        let mut index = [0u8; 32];
+        for byte in self.forks.keys() {
+            index[*byte as usize / 8] |= 1 << (byte % 8);
+        }
+        data.extend_from_slice(&index);
+
+        for fork in self.forks.values() {
+            let address = fork.node.address.as_ref().ok_or_else(|| {
+                Error::Custom("mantaray fork has not been saved".to_string())
+            })?;
+            if address.len() != self.ref_bytes_size {
+                return Err(Error::Custom(format!(
+                    "fork reference size {} does not match reference size {}",
+                    address.len(),
+                    self.ref_bytes_size
+                )));
+            }
+
+            data.push(fork.node.node_type);
+            data.push(fork.prefix.len() as u8);
+            let mut prefix = [0u8; FORK_PREFIX_MAX_SIZE];
+            prefix[..fork.prefix.len()].copy_from_slice(&fork.prefix);
+            data.extend_from_slice(&prefix);
+            data.extend_from_slice(address);
+
+            if fork.node.node_type & TYPE_WITH_METADATA != 0 {
+                let metadata = fork.node.metadata.clone().unwrap_or_default();
+                let mut json = serde_json::to_vec(&metadata)
+                    .map_err(|e| Error::Custom(format!("invalid mantaray metadata: {}", e)))?;
+                // Padding follows the reference implementation, which adds a
+                // full segment when the size is already aligned.
+                let size_with_length = json.len() + FORK_METADATA_BYTES_SIZE;
+                if size_with_length < OBFUSCATION_KEY_SIZE {
+                    json.resize(json.len() + OBFUSCATION_KEY_SIZE - size_with_length, b'\n');
+                } else if size_with_length > OBFUSCATION_KEY_SIZE {
+                    let padding = OBFUSCATION_KEY_SIZE - size_with_length % OBFUSCATION_KEY_SIZE;
+                    json.resize(json.len() + padding, b'\n');
+                }
+                let size = u16::try_from(json.len())
+                    .map_err(|_| Error::Custom("mantaray metadata too large".to_string()))?;
+                data.extend_from_slice(&size.to_be_bytes());
+                data.extend_from_slice(&json);
+            }
+        }
+
+        Ok(xor_with_key(&data, &self.obfuscation_key))
+    }
+
+    /// Add an entry at the given path, replacing any existing entry and its
+    /// metadata.
+    ///
+    /// Every node whose content changes must be loaded, otherwise an error is
+    /// returned and the tree is left untouched.
+    pub fn add(
+        &mut self,
+        path: &[u8],
+        entry: &[u8],
+        metadata: BTreeMap<String, String>,
+    ) -> Result<(), Error> {
+        if !self.loaded {
+            return Err(Error::Custom("mantaray node is not loaded".to_string()));
+        }
+        if entry.len() != self.ref_bytes_size {
+            return Err(Error::Custom(format!(
+                "entry size {} does not match reference size {}",
+                entry.len(),
+                self.ref_bytes_size
+            )));
+        }
+
+        if path.is_empty() {
+            self.entry = entry.to_vec();
+            self.node_type |= TYPE_VALUE;
+            if metadata.is_empty() {
+                self.metadata = None;
+                self.node_type &= !TYPE_WITH_METADATA;
+            } else {
+                self.metadata = Some(metadata);
+                self.node_type |= TYPE_WITH_METADATA;
+            }
+            self.address = None;
+            return Ok(());
+        }
+
+        let common = match self.forks.get(&path[0]) {
+            None => {
+                let split = path.len().min(FORK_PREFIX_MAX_SIZE);
+                let (prefix, rest) = path.split_at(split);
+                let mut node = self.new_child();
+                node.add(rest, entry, metadata)?;
+                node.update_with_path_separator(prefix);
+                self.forks.insert(
+                    path[0],
+                    Fork {
+                        prefix: prefix.to_vec(),
+                        node,
+                    },
+                );
+                self.node_type |= TYPE_EDGE;
+                self.address = None;
+                return Ok(());
+            }
+            Some(fork) => {
+                let common = common_prefix_length(&fork.prefix, path);
+                if common == fork.prefix.len() && !fork.node.loaded {
+                    return Err(Error::Custom("mantaray node is not loaded".to_string()));
+                }
+                common
+            }
+        };
+
+        let mut fork = self.forks.remove(&path[0]).expect("fork exists");
+        let mut node = if common < fork.prefix.len() {
+            let rest = fork.prefix.split_off(common);
+            let mut moved = fork.node;
+            moved.update_with_path_separator(&rest);
+            let mut node = self.new_child();
+            node.forks.insert(
+                rest[0],
+                Fork {
+                    prefix: rest,
+                    node: moved,
+                },
+            );
+            node.node_type |= TYPE_EDGE;
+            node
+        } else {
+            fork.node
+        };
+
+        node.update_with_path_separator(path);
+        let result = node.add(&path[common..], entry, metadata);
+        self.forks.insert(
+            path[0],
+            Fork {
+                prefix: fork.prefix,
+                node,
+            },
+        );
+        result?;
+        self.node_type |= TYPE_EDGE;
+        self.address = None;
+        Ok(())
+    }
+
+    /// Remove the entry at the given path.
+    ///
+    /// Nodes that still lead to other entries are kept and only lose their
+    /// value.
+    pub fn remove(&mut self, path: &[u8]) -> Result<(), Error> {
+        if path.is_empty() {
+            return Err(Error::Custom("path cannot be empty".to_string()));
+        }
+        if !self.loaded {
+            return Err(Error::Custom("mantaray node is not loaded".to_string()));
+        }
+
+        let fork = self
+            .forks
+            .get_mut(&path[0])
+            .filter(|fork| path.starts_with(&fork.prefix))
+            .ok_or_else(|| not_found(path))?;
+        let rest = &path[fork.prefix.len()..];
+
+        if rest.is_empty() {
+            if !fork.node.loaded {
+                return Err(Error::Custom("mantaray node is not loaded".to_string()));
+            }
+            if !fork.node.is_value() {
+                return Err(not_found(path));
+            }
+            if fork.node.forks.is_empty() {
+                self.forks.remove(&path[0]);
+                if self.forks.is_empty() {
+                    self.node_type &= !TYPE_EDGE;
+                }
+            } else {
+                let node = &mut fork.node;
+                node.entry = Vec::new();
+                node.metadata = None;
+                node.node_type &= !(TYPE_VALUE | TYPE_WITH_METADATA);
+                node.address = None;
+            }
+        } else {
+            fork.node.remove(rest)?;
+        }
+
+        self.address = None;
+        Ok(())
+    }
+
+    /// Find the node at the given path.
+    ///
+    /// Nodes that are not loaded are treated as having no forks.
+    pub fn find(&self, path: &[u8]) -> Option<&MantarayNode> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        let fork = self.forks.get(&path[0])?;
+        if !path.starts_with(&fork.prefix) {
+            return None;
+        }
+        fork.node.find(&path[fork.prefix.len()..])
+    }
+
+    fn new_child(&self) -> MantarayNode {
+        MantarayNode {
+            obfuscation_key: self.obfuscation_key,
+            ref_bytes_size: self.ref_bytes_size,
+            ..MantarayNode::new()
+        }
+    }
+
+    fn update_with_path_separator(&mut self, path: &[u8]) {
+        let has_separator = path
+            .iter()
+            .skip(1)
+            .position(|byte| *byte == PATH_SEPARATOR)
+            .is_some_and(|index| index > 0);
+        if has_separator {
+            self.node_type |= TYPE_WITH_PATH_SEPARATOR;
+        } else {
+            self.node_type &= !TYPE_WITH_PATH_SEPARATOR;
+        }
+    }
+}
+
+fn xor_with_key(data: &[u8], key: &[u8; OBFUSCATION_KEY_SIZE]) -> Vec<u8> {
+    data.iter()
+        .enumerate()
+        .map(|(i, byte)| {
+            if i < OBFUSCATION_KEY_SIZE {
+                *byte
+            } else {
+                byte ^ key[i % OBFUSCATION_KEY_SIZE]
+            }
+        })
+        .collect()
+}
+
+fn read(data: &[u8], offset: usize, length: usize) -> Result<&[u8], Error> {
+    data.get(offset..offset + length)
+        .ok_or_else(|| Error::Custom("mantaray node is truncated".to_string()))
+}
+
+fn common_prefix_length(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+fn not_found(path: &[u8]) -> Error {
+    Error::Custom(format!(
+        "path not found in manifest: {}",
+        String::from_utf8_lossy(path)
+    ))
+}
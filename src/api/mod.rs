@@ -4,12 +4,21 @@ pub mod debug;
 pub mod bmt;
 pub mod bytes;
 pub mod bzz;
+pub mod cheque;
 pub mod chunk;
 pub mod envelope;
 pub mod feed;
 pub mod grantee;
 pub mod gsoc;
+pub(crate) mod headers;
+pub mod manifest;
+pub mod mantaray;
 pub mod pinning;
+pub mod progress;
 pub mod pss;
 pub mod rchash;
+pub mod signer;
 pub mod soc;
+pub mod stamper;
+pub mod tag;
+pub mod typed_bytes;
@@ -0,0 +1,95 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Progress reporting of uploads.
+//!
+//! An upload goes through two phases: the data is sent to the node, which
+//! then splits it into chunks and syncs them with the network. The first
+//! phase is reported while the request body is streamed, the second one by
+//! polling the tag of the upload.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::StreamExt;
+use reqwest::{Body, Client};
+
+use crate::api::bytes::UploadResult;
+use crate::api::tag::{get_tag, poll_until_synced, Tag};
+use crate::error::Error;
+
+/// The size of the pieces the request body is streamed in.
+const PROGRESS_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A progress update of an upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadProgress {
+    /// `sent` of `total` bytes have been handed to the connection.
+    Sent { sent: u64, total: u64 },
+    /// The state of the tag of the upload on the node.
+    Tag(Tag),
+}
+
+/// Receives progress updates of an upload.
+///
+/// Implemented for closures taking an [`UploadProgress`].
+pub trait ProgressObserver: Send + Sync {
+    fn on_progress(&self, progress: UploadProgress);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(UploadProgress) + Send + Sync,
+{
+    fn on_progress(&self, progress: UploadProgress) {
+        self(progress)
+    }
+}
+
+/// A request body reporting the bytes sent to `observer`.
+pub(crate) fn observed_body(data: Vec<u8>, observer: Arc<dyn ProgressObserver>) -> Body {
+    let data = Bytes::from(data);
+    let total = data.len() as u64;
+    let pieces: Vec<Bytes> = (0..data.len())
+        .step_by(PROGRESS_CHUNK_SIZE)
+        .map(|start| data.slice(start..(start + PROGRESS_CHUNK_SIZE).min(data.len())))
+        .collect();
+
+    let mut sent = 0;
+    let stream = futures::stream::iter(pieces).map(move |piece| {
+        sent += piece.len() as u64;
+        observer.on_progress(UploadProgress::Sent { sent, total });
+        Ok::<_, std::io::Error>(piece)
+    });
+    Body::wrap_stream(stream)
+}
+
+/// Report the state of the tag of a finished upload, if it has one.
+pub(crate) async fn report_tag(
+    client: &Client,
+    base_url: &str,
+    result: &UploadResult,
+    observer: &dyn ProgressObserver,
+) -> Result<(), Error> {
+    if let Some(uid) = result.tag_uid {
+        let tag = get_tag(client, base_url, uid).await?;
+        observer.on_progress(UploadProgress::Tag(tag));
+    }
+    Ok(())
+}
+
+/// Report the state of a tag to `observer` until every chunk of the upload
+/// has been synced, or `timeout` elapses.
+pub async fn watch_tag(
+    client: &Client,
+    base_url: &str,
+    uid: u32,
+    timeout: Duration,
+    observer: &dyn ProgressObserver,
+) -> Result<Tag, Error> {
+    poll_until_synced(client, base_url, uid, timeout, |tag| {
+        observer.on_progress(UploadProgress::Tag(tag.clone()))
+    })
+    .await
+}
@@ -17,7 +17,7 @@ pub async fn rchash(
     anchor2: &str,
 ) -> Result<f64, Error> {
     let url = format!("{}/{}/{}/{}/{}", base_url, RCHASH_ENDPOINT, depth, anchor1, anchor2);
-    let response = client.get(&url).send().await?;
+    let response = client.get(&url).send().await?.error_for_status()?;
     let rchash_response: RchashResponse = response.json().await?;
     Ok(rchash_response.duration_seconds)
 }
@@ -0,0 +1,121 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ethereum compatible signing of chunks and postage stamps.
+//! https://github.com/ethersphere/bee/blob/master/pkg/crypto/signer.go
+//!
+//! Data is signed as an Ethereum signed message, that is the keccak256 hash
+//! of `"\x19Ethereum Signed Message:\n" || len(data) || data`. Signatures are
+//! 65 bytes long: `r || s || v` with `v` being 27 or 28.
+
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use sha3::{Digest, Keccak256};
+
+use crate::error::Error;
+
+/// The size of a signature in bytes.
+pub const SIGNATURE_SIZE: usize = 65;
+/// The size of an Ethereum address in bytes.
+pub const ADDRESS_SIZE: usize = 20;
+
+/// Signs data on behalf of an Ethereum address.
+pub trait Signer: Send + Sync {
+    /// The Ethereum address of the signer.
+    fn address(&self) -> [u8; ADDRESS_SIZE];
+
+    /// Sign `data` as an Ethereum signed message.
+    fn sign(&self, data: &[u8]) -> Result<[u8; SIGNATURE_SIZE], Error>;
+}
+
+/// A [`Signer`] holding a secp256k1 private key.
+#[derive(Clone)]
+pub struct PrivateKeySigner {
+    key: SigningKey,
+}
+
+impl PrivateKeySigner {
+    /// Create a signer from a 32 byte private key.
+    pub fn new(private_key: &[u8]) -> Result<Self, Error> {
+        let key = SigningKey::from_slice(private_key)
+            .map_err(|e| Error::Custom(format!("invalid private key: {}", e)))?;
+        Ok(Self { key })
+    }
+
+    /// Create a signer from a hex encoded private key, with or without `0x`.
+    pub fn from_hex(private_key: &str) -> Result<Self, Error> {
+        let private_key = private_key.strip_prefix("0x").unwrap_or(private_key);
+        let bytes = hex::decode(private_key)
+            .map_err(|e| Error::Custom(format!("invalid private key: {}", e)))?;
+        Self::new(&bytes)
+    }
+
+    /// Sign a 32 byte hash as is, without the Ethereum message prefix.
+    pub fn sign_hash(&self, hash: &[u8; 32]) -> Result<[u8; SIGNATURE_SIZE], Error> {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(hash)
+            .map_err(|e| Error::Custom(format!("signing failed: {}", e)))?;
+
+        let mut bytes = [0u8; SIGNATURE_SIZE];
+        bytes[..64].copy_from_slice(&signature.to_bytes());
+        bytes[64] = 27 + recovery_id.to_byte();
+        Ok(bytes)
+    }
+}
+
+impl Signer for PrivateKeySigner {
+    fn address(&self) -> [u8; ADDRESS_SIZE] {
+        public_key_address(self.key.verifying_key())
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<[u8; SIGNATURE_SIZE], Error> {
+        self.sign_hash(&ethereum_message_hash(data))
+    }
+}
+
+impl std::fmt::Debug for PrivateKeySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivateKeySigner")
+            .field("address", &hex::encode(self.address()))
+            .finish()
+    }
+}
+
+/// The hash that is signed for `data` as an Ethereum signed message.
+pub fn ethereum_message_hash(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", data.len()));
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Recover the address that signed `hash` without the Ethereum message
+/// prefix.
+pub fn recover_hash_address(hash: &[u8; 32], signature: &[u8]) -> Result<[u8; ADDRESS_SIZE], Error> {
+    if signature.len() != SIGNATURE_SIZE {
+        return Err(Error::Custom(format!("invalid signature length: {}", signature.len())));
+    }
+    let recovery_id = signature[64]
+        .checked_sub(27)
+        .and_then(RecoveryId::from_byte)
+        .ok_or_else(|| Error::Custom(format!("invalid signature recovery id: {}", signature[64])))?;
+    let signature = Signature::from_slice(&signature[..64])
+        .map_err(|e| Error::Custom(format!("invalid signature: {}", e)))?;
+
+    let key = VerifyingKey::recover_from_prehash(hash, &signature, recovery_id)
+        .map_err(|e| Error::Custom(format!("signature recovery failed: {}", e)))?;
+    Ok(public_key_address(&key))
+}
+
+/// Recover the address that signed `data` as an Ethereum signed message.
+pub fn recover_address(data: &[u8], signature: &[u8]) -> Result<[u8; ADDRESS_SIZE], Error> {
+    recover_hash_address(&ethereum_message_hash(data), signature)
+}
+
+fn public_key_address(key: &VerifyingKey) -> [u8; ADDRESS_SIZE] {
+    let point = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+    let mut address = [0u8; ADDRESS_SIZE];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
@@ -2,6 +2,8 @@ use reqwest::{Client, Error};
 use serde::{Deserialize, Serialize};
 
 use crate::api::bytes::UploadOptions;
+use crate::api::headers::{with_postage, Postage};
+use crate::api::stamper::Stamp;
 
 const SOC_ENDPOINT: &str = "soc";
 
@@ -14,12 +16,46 @@ pub async fn upload(
     data: Vec<u8>,
     postage_batch_id: &str,
     options: Option<UploadOptions>,
+) -> Result<UploadResult, Error> {
+    let postage = Postage::BatchId(postage_batch_id);
+    upload_with_postage(client, base_url, owner, identifier, signature, data, postage, options).await
+}
+
+/// Upload a single owner chunk with a stamp signed by the batch owner.
+///
+/// The node does not need to own the batch, so chunks can be uploaded
+/// through gateways.
+#[allow(clippy::too_many_arguments)]
+pub async fn upload_with_stamp(
+    client: &Client,
+    base_url: &str,
+    owner: &str,
+    identifier: &str,
+    signature: &str,
+    data: Vec<u8>,
+    stamp: &Stamp,
+    options: Option<UploadOptions>,
+) -> Result<UploadResult, Error> {
+    let postage = Postage::Stamp(stamp);
+    upload_with_postage(client, base_url, owner, identifier, signature, data, postage, options).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upload_with_postage(
+    client: &Client,
+    base_url: &str,
+    owner: &str,
+    identifier: &str,
+    signature: &str,
+    data: Vec<u8>,
+    postage: Postage<'_>,
+    options: Option<UploadOptions>,
 ) -> Result<UploadResult, Error> {
     let url = format!("{}/{}/{}/{}", base_url, SOC_ENDPOINT, owner, identifier);
     let mut request_builder = client.post(&url).body(data);
 
     request_builder = request_builder.header("content-type", "application/octet-stream");
-    request_builder = request_builder.header("swarm-postage-batch-id", postage_batch_id);
+    request_builder = with_postage(request_builder, postage);
     request_builder = request_builder.query(&[("sig", signature)]);
 
     if let Some(opts) = options {
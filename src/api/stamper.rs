@@ -0,0 +1,277 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client-side stamping of chunks.
+//! https://github.com/ethersphere/bee-js/blob/main/src/stamper/stamper.ts
+//!
+//! A postage batch of depth `d` is split into 2^16 collision buckets, picked
+//! by the first 16 bits of a chunk address, each holding up to 2^(d-16)
+//! chunks. The [`Stamper`] assigns every chunk the next free slot of its
+//! bucket and signs the stamp with the key of the batch owner, so chunks can
+//! be uploaded through nodes that do not own the batch.
+
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha3::{Digest, Keccak256};
+
+use crate::api::signer::{recover_address, Signer, ADDRESS_SIZE, SIGNATURE_SIZE};
+use crate::error::Error;
+
+/// The number of address bits selecting a collision bucket.
+pub const BUCKET_DEPTH: u8 = 16;
+/// The number of collision buckets of a batch.
+pub const BUCKET_COUNT: usize = 1 << BUCKET_DEPTH;
+
+/// The size of a serialized stamp in bytes.
+pub const STAMP_SIZE: usize = 113;
+
+/// The deepest batch whose bucket slots fit into a `u32`.
+const MAX_DEPTH: u8 = BUCKET_DEPTH + 31;
+const STATE_HEADER_SIZE: usize = 33;
+
+/// A signed postage stamp of a chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stamp {
+    pub batch_id: [u8; 32],
+    /// The bucket and the slot within the bucket, both as big endian `u32`.
+    pub index: [u8; 8],
+    /// The time of stamping in nanoseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub signature: [u8; SIGNATURE_SIZE],
+}
+
+impl Stamp {
+    /// The collision bucket of the stamped chunk.
+    pub fn bucket(&self) -> u32 {
+        u32::from_be_bytes(self.index[..4].try_into().unwrap())
+    }
+
+    /// The slot of the stamped chunk within its bucket.
+    pub fn slot(&self) -> u32 {
+        u32::from_be_bytes(self.index[4..].try_into().unwrap())
+    }
+
+    /// Serialize the stamp as `batch ID || index || timestamp || signature`.
+    pub fn to_bytes(&self) -> [u8; STAMP_SIZE] {
+        let mut bytes = [0u8; STAMP_SIZE];
+        bytes[..32].copy_from_slice(&self.batch_id);
+        bytes[32..40].copy_from_slice(&self.index);
+        bytes[40..48].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[48..].copy_from_slice(&self.signature);
+        bytes
+    }
+
+    /// Deserialize a stamp created with [`Stamp::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != STAMP_SIZE {
+            return Err(Error::Custom(format!("invalid stamp length: {}", bytes.len())));
+        }
+        Ok(Self {
+            batch_id: bytes[..32].try_into().unwrap(),
+            index: bytes[32..40].try_into().unwrap(),
+            timestamp: u64::from_be_bytes(bytes[40..48].try_into().unwrap()),
+            signature: bytes[48..].try_into().unwrap(),
+        })
+    }
+
+    /// Recover the address of the batch owner that signed the stamp for a
+    /// chunk.
+    pub fn recover_owner(&self, chunk_address: &[u8; 32]) -> Result<[u8; ADDRESS_SIZE], Error> {
+        let digest = stamp_digest(chunk_address, &self.batch_id, &self.index, self.timestamp);
+        recover_address(&digest, &self.signature)
+    }
+
+    /// Verify that the stamp of a chunk was signed by `owner`.
+    ///
+    /// Also fails if the index of the stamp does not belong to the bucket of
+    /// the chunk.
+    pub fn verify(&self, chunk_address: &[u8; 32], owner: &[u8; ADDRESS_SIZE]) -> Result<(), Error> {
+        if self.bucket() != u32::from(bucket_of(chunk_address)) {
+            return Err(Error::Custom(format!(
+                "stamp bucket {} does not match chunk bucket {}",
+                self.bucket(),
+                bucket_of(chunk_address)
+            )));
+        }
+        let signer = self.recover_owner(chunk_address)?;
+        if &signer != owner {
+            return Err(Error::Custom(format!(
+                "stamp signed by {} instead of the batch owner {}",
+                hex::encode(signer),
+                hex::encode(owner)
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&[u8]> for Stamp {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+/// The collision bucket of a chunk address.
+pub fn bucket_of(chunk_address: &[u8; 32]) -> u16 {
+    u16::from_be_bytes([chunk_address[0], chunk_address[1]])
+}
+
+/// The digest signed by the batch owner for a stamp.
+pub fn stamp_digest(chunk_address: &[u8; 32], batch_id: &[u8; 32], index: &[u8; 8], timestamp: u64) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(chunk_address);
+    hasher.update(batch_id);
+    hasher.update(index);
+    hasher.update(timestamp.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// Stamps chunks on behalf of the owner of a postage batch.
+pub struct Stamper<S: Signer> {
+    signer: S,
+    batch_id: [u8; 32],
+    depth: u8,
+    buckets: Vec<u32>,
+}
+
+impl<S: Signer> Stamper<S> {
+    /// Create a stamper for a batch with no chunks stamped yet.
+    ///
+    /// ## Arguments
+    ///
+    /// * `signer` - The owner of the batch.
+    /// * `batch_id` - The hex encoded ID of the batch.
+    /// * `depth` - The depth of the batch.
+    pub fn new(signer: S, batch_id: &str, depth: u8) -> Result<Self, Error> {
+        let bytes = hex::decode(batch_id).map_err(|e| Error::Custom(format!("invalid batch ID: {}", e)))?;
+        let batch_id: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| Error::Custom(format!("invalid batch ID length: {}", batch_id)))?;
+        Self::with_buckets(signer, batch_id, depth, vec![0; BUCKET_COUNT])
+    }
+
+    /// Restore a stamper from a state created with [`Stamper::state`].
+    pub fn from_state(signer: S, state: &[u8]) -> Result<Self, Error> {
+        if state.len() != STATE_HEADER_SIZE + BUCKET_COUNT * 4 {
+            return Err(Error::Custom(format!("invalid stamper state length: {}", state.len())));
+        }
+        let batch_id: [u8; 32] = state[..32].try_into().unwrap();
+        let depth = state[32];
+        let buckets = state[STATE_HEADER_SIZE..]
+            .chunks_exact(4)
+            .map(|count| u32::from_be_bytes(count.try_into().unwrap()))
+            .collect();
+        Self::with_buckets(signer, batch_id, depth, buckets)
+    }
+
+    /// Restore a stamper from a file written with [`Stamper::save`].
+    pub async fn load(signer: S, path: impl AsRef<Path>) -> Result<Self, Error> {
+        let state = tokio::fs::read(path).await?;
+        Self::from_state(signer, &state)
+    }
+
+    fn with_buckets(signer: S, batch_id: [u8; 32], depth: u8, buckets: Vec<u32>) -> Result<Self, Error> {
+        if depth <= BUCKET_DEPTH || depth > MAX_DEPTH {
+            return Err(Error::Custom(format!(
+                "batch depth must be between {} and {}, got {}",
+                BUCKET_DEPTH + 1,
+                MAX_DEPTH,
+                depth
+            )));
+        }
+        let stamper = Self {
+            signer,
+            batch_id,
+            depth,
+            buckets,
+        };
+        if let Some(bucket) = stamper.buckets.iter().position(|&count| count > stamper.bucket_capacity()) {
+            return Err(Error::Custom(format!("bucket {} exceeds the batch capacity", bucket)));
+        }
+        Ok(stamper)
+    }
+
+    /// The signer of the stamps.
+    pub fn signer(&self) -> &S {
+        &self.signer
+    }
+
+    /// The ID of the batch.
+    pub fn batch_id(&self) -> &[u8; 32] {
+        &self.batch_id
+    }
+
+    /// The depth of the batch.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The number of chunks a bucket can hold.
+    pub fn bucket_capacity(&self) -> u32 {
+        1 << (self.depth - BUCKET_DEPTH)
+    }
+
+    /// The number of chunks stamped in every bucket, indexed by bucket.
+    pub fn buckets(&self) -> &[u32] {
+        &self.buckets
+    }
+
+    /// Stamp a chunk with the current time.
+    pub fn stamp(&mut self, chunk_address: &[u8; 32]) -> Result<Stamp, Error> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| Error::Custom(format!("invalid system time: {}", e)))?
+            .as_nanos() as u64;
+        self.stamp_at(chunk_address, timestamp)
+    }
+
+    /// Stamp a chunk with the given timestamp in nanoseconds.
+    ///
+    /// Fails if the bucket of the chunk is full.
+    pub fn stamp_at(&mut self, chunk_address: &[u8; 32], timestamp: u64) -> Result<Stamp, Error> {
+        let bucket = bucket_of(chunk_address);
+        let slot = self.buckets[bucket as usize];
+        if slot >= self.bucket_capacity() {
+            return Err(Error::Custom(format!("bucket {} is full", bucket)));
+        }
+
+        let mut index = [0u8; 8];
+        index[..4].copy_from_slice(&u32::from(bucket).to_be_bytes());
+        index[4..].copy_from_slice(&slot.to_be_bytes());
+        let digest = stamp_digest(chunk_address, &self.batch_id, &index, timestamp);
+        let signature = self.signer.sign(&digest)?;
+
+        self.buckets[bucket as usize] += 1;
+        Ok(Stamp {
+            batch_id: self.batch_id,
+            index,
+            timestamp,
+            signature,
+        })
+    }
+
+    /// The bucket state of the stamper, to resume stamping later with
+    /// [`Stamper::from_state`].
+    pub fn state(&self) -> Vec<u8> {
+        let mut state = Vec::with_capacity(STATE_HEADER_SIZE + BUCKET_COUNT * 4);
+        state.extend_from_slice(&self.batch_id);
+        state.push(self.depth);
+        for count in &self.buckets {
+            state.extend_from_slice(&count.to_be_bytes());
+        }
+        state
+    }
+
+    /// Write the bucket state to `path`, replacing it atomically.
+    pub async fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        tokio::fs::write(&temporary, self.state()).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+}
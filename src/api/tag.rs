@@ -0,0 +1,158 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bee-js `tag` module implementation.
+//! https://github.com/ethersphere/bee-js/blob/main/src/modules/tag.ts
+//!
+//! Tags track how far the chunks of an upload have been processed by the
+//! node and synced to the network.
+//!
+//! ## Endpoints
+//!
+//! - `POST /tags`: Creates a new tag.
+//! - `GET /tags`: Lists tags.
+//! - `GET /tags/{uid}`: Retrieves a tag.
+//! - `PATCH /tags/{uid}`: Sets the root reference of a tag.
+//! - `DELETE /tags/{uid}`: Deletes a tag.
+
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+const ENDPOINT: &str = "tags";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The progress of an upload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    pub uid: u32,
+    /// The number of chunks the upload was split into.
+    pub split: u64,
+    /// The number of chunks that were already stored on the node.
+    pub seen: u64,
+    /// The number of chunks stored on the node.
+    pub stored: u64,
+    /// The number of chunks sent to the network.
+    pub sent: u64,
+    /// The number of chunks synced with the network.
+    pub synced: u64,
+    /// The root reference of the upload, once known.
+    #[serde(default)]
+    pub address: String,
+    #[serde(rename = "startedAt")]
+    pub started_at: String,
+}
+
+impl Tag {
+    /// Whether every chunk of the upload has been synced with the network.
+    ///
+    /// Chunks the node already had are not synced again.
+    pub fn is_synced(&self) -> bool {
+        self.split > 0 && self.synced + self.seen >= self.split
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    tags: Option<Vec<Tag>>,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdateTagRequest<'a> {
+    address: &'a str,
+}
+
+/// Create a new tag to pass to an upload with `UploadOptions::tag`.
+pub async fn create_tag(client: &Client, base_url: &str) -> Result<Tag, Error> {
+    let url = format!("{}/{}", base_url, ENDPOINT);
+    let response = client.post(&url).send().await?.error_for_status()?;
+    Ok(response.json().await?)
+}
+
+/// Retrieve a tag.
+pub async fn get_tag(client: &Client, base_url: &str, uid: u32) -> Result<Tag, Error> {
+    let url = format!("{}/{}/{}", base_url, ENDPOINT, uid);
+    let response = client.get(&url).send().await?.error_for_status()?;
+    Ok(response.json().await?)
+}
+
+/// List tags.
+///
+/// ## Arguments
+///
+/// * `offset` - The number of tags to skip.
+/// * `limit` - The maximum number of tags to return.
+pub async fn list_tags(
+    client: &Client,
+    base_url: &str,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<Vec<Tag>, Error> {
+    let url = format!("{}/{}", base_url, ENDPOINT);
+    let mut request_builder = client.get(&url);
+    if let Some(offset) = offset {
+        request_builder = request_builder.query(&[("offset", offset)]);
+    }
+    if let Some(limit) = limit {
+        request_builder = request_builder.query(&[("limit", limit)]);
+    }
+
+    let response = request_builder.send().await?.error_for_status()?;
+    let tags_response: TagsResponse = response.json().await?;
+    Ok(tags_response.tags.unwrap_or_default())
+}
+
+/// Set the root reference of the upload tracked by a tag.
+pub async fn update_tag(client: &Client, base_url: &str, uid: u32, reference: &str) -> Result<(), Error> {
+    let url = format!("{}/{}/{}", base_url, ENDPOINT, uid);
+    client
+        .patch(&url)
+        .json(&UpdateTagRequest { address: reference })
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Delete a tag.
+pub async fn delete_tag(client: &Client, base_url: &str, uid: u32) -> Result<(), Error> {
+    let url = format!("{}/{}/{}", base_url, ENDPOINT, uid);
+    client.delete(&url).send().await?.error_for_status()?;
+    Ok(())
+}
+
+/// Poll a tag until every chunk of the upload has been synced.
+///
+/// Returns the final state of the tag, or an error if the upload is not
+/// synced within `timeout`.
+pub async fn wait_for_sync(client: &Client, base_url: &str, uid: u32, timeout: Duration) -> Result<Tag, Error> {
+    poll_until_synced(client, base_url, uid, timeout, |_| {}).await
+}
+
+/// Like [`wait_for_sync`], passing every polled state of the tag to `on_tag`.
+pub(crate) async fn poll_until_synced(
+    client: &Client,
+    base_url: &str,
+    uid: u32,
+    timeout: Duration,
+    on_tag: impl Fn(&Tag),
+) -> Result<Tag, Error> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let tag = get_tag(client, base_url, uid).await?;
+        on_tag(&tag);
+        if tag.is_synced() {
+            return Ok(tag);
+        }
+        if tokio::time::Instant::now() + POLL_INTERVAL > deadline {
+            return Err(Error::Custom(format!(
+                "tag {} not synced after {:?}: {} of {} chunks synced",
+                uid, timeout, tag.synced + tag.seen, tag.split
+            )));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
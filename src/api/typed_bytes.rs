@@ -0,0 +1,110 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validated hex encoded identifiers.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/typed-bytes.ts
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+macro_rules! typed_bytes {
+    ($(#[$meta:meta])* $name:ident, $length:expr, $prefix:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(String);
+
+        impl $name {
+            /// The length of the identifier in bytes.
+            pub const LENGTH: usize = $length;
+
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+                if bytes.len() != Self::LENGTH {
+                    return Err(Error::Custom(format!(
+                        "invalid {} length: {}",
+                        stringify!($name),
+                        bytes.len()
+                    )));
+                }
+                Ok(Self(format!("{}{}", $prefix, hex::encode(bytes))))
+            }
+
+            /// The hex encoded identifier.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn to_bytes(&self) -> [u8; $length] {
+                let mut bytes = [0u8; $length];
+                hex::decode_to_slice(&self.0[$prefix.len()..], &mut bytes).expect("validated on creation");
+                bytes
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            /// Parse a hex encoded identifier, with or without `0x`.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let hex = s.strip_prefix("0x").unwrap_or(s);
+                let bytes = hex::decode(hex)
+                    .map_err(|e| Error::Custom(format!("invalid {} {}: {}", stringify!($name), s, e)))?;
+                Self::from_bytes(&bytes)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                other.parse::<Self>().is_ok_and(|other| *self == other)
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self == *other
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                value.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+typed_bytes!(
+    /// The ID of a postage batch, hex encoded without `0x`.
+    BatchId,
+    32,
+    ""
+);
+
+typed_bytes!(
+    /// The hash of a blockchain transaction, hex encoded with `0x`.
+    TransactionHash,
+    32,
+    "0x"
+);
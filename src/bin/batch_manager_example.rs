@@ -0,0 +1,38 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! In this example we will keep a postage batch topped up and diluted.
+//!
+//! `cargo run --bin batch_manager_example --release`
+
+use std::env;
+
+use bee_rs::api::debug::batch_manager::{BatchManager, BatchPolicy};
+use bee_rs::utils::duration::{format_duration, parse_duration};
+
+#[tokio::main]
+async fn main() {
+    // The node API endpoint
+    let bee_debug_url = env::var("BEE_DEBUG_URL").unwrap_or_else(|_| "http://localhost:1635".to_string());
+    let batch_id = env::var("BATCH_ID").expect("BATCH_ID is not set");
+    // How often to check the batch, e.g. "30m" or "1d 6h"
+    let interval = parse_duration(&env::var("CHECK_INTERVAL").unwrap_or_else(|_| "1h".to_string())).unwrap();
+    let manager = BatchManager::new(&bee_debug_url, BatchPolicy::default()).unwrap();
+
+    println!("Checking batch {} every {}", batch_id, format_duration(interval));
+    let result = manager
+        .monitor(
+            &batch_id,
+            interval,
+            |report| {
+                let ttl = report.batch.ttl().map_or_else(|| "unknown".to_string(), format_duration);
+                println!("Batch {} changed: {:?}, TTL {}", report.batch.batch_id, report.actions, ttl);
+            },
+            |e| eprintln!("Retrying after error: {}", e),
+        )
+        .await;
+
+    if let Err(e) = result {
+        eprintln!("Error maintaining batch: {}", e);
+    }
+}
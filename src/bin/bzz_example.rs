@@ -1,4 +1,4 @@
-use bee_rs::api::bzz::{download_file, upload_file, FileUploadOptions};
+use bee_rs::api::bzz::{download_file, list_collection, upload_file, FileUploadOptions};
 use bee_rs::api::bytes::{DownloadOptions, RedundantUploadOptions, UploadOptions};
 use std::env;
 
@@ -63,4 +63,15 @@ async fn main() {
         }
         Err(e) => eprintln!("Error downloading file: {}", e),
     }
+
+    // Example: List the files of a collection
+    let collection_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f"; // Replace with a valid collection reference
+    match list_collection(&client, BEE_API_URL, collection_reference).await {
+        Ok(files) => {
+            for file in files {
+                println!("{} {} {} bytes", file.reference, file.path, file.size);
+            }
+        }
+        Err(e) => eprintln!("Error listing collection: {}", e),
+    }
 }
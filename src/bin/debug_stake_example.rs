@@ -0,0 +1,26 @@
+use bee_rs::api::debug::stake::BeeDebugStakeClient;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let bee_debug_stake_client = BeeDebugStakeClient::new("http://localhost:1633")?;
+
+    println!("Getting staked amount...");
+    match bee_debug_stake_client.get_stake().await {
+        Ok(response) => println!("Staked amount: {} PLUR", response.staked_amount),
+        Err(e) => println!("Failed to get staked amount: {}", e),
+    }
+
+    println!("\nGetting withdrawable stake...");
+    match bee_debug_stake_client.get_withdrawable_stake().await {
+        Ok(response) => println!("Withdrawable stake: {} PLUR", response.withdrawable_amount),
+        Err(e) => println!("Failed to get withdrawable stake: {}", e),
+    }
+
+    println!("\nGetting redistribution state...");
+    match bee_debug_stake_client.get_redistribution_state().await {
+        Ok(response) => println!("Redistribution state: {:#?}", response),
+        Err(e) => println!("Failed to get redistribution state: {}", e),
+    }
+
+    Ok(())
+}
@@ -7,6 +7,7 @@
 //! `cargo run --example debug_stamps_example --release`
 
 use bee_rs::api::debug::stamps::BeeDebugStampsClient;
+use bee_rs::utils::tokens::BZZ;
 use dotenv::dotenv;
 use std::env;
 
@@ -17,27 +18,27 @@ async fn main() {
     let client = BeeDebugStampsClient::new(&bee_debug_url).unwrap();
 
     // Create a new postage batch
-    let amount = "10000";
+    let amount = BZZ::from_plur(10000);
     let depth = 20;
     let gas_price = None;
     let immutable_flag = None;
     let label = Some("my-batch");
 
-    match client.create_postage_batch(amount, depth, gas_price, immutable_flag, label).await {
+    match client.create_postage_batch(&amount, depth, gas_price, immutable_flag, label).await {
         Ok(batch_id) => {
             println!("Created postage batch with ID: {}", batch_id);
 
             // Top up the batch
-            let top_up_amount = "5000";
-            match client.top_up_batch(&batch_id, top_up_amount).await {
-                Ok(new_batch_id) => println!("Topped up batch, new ID: {}", new_batch_id),
+            let top_up_amount = BZZ::from_plur(5000);
+            match client.top_up_batch(batch_id.as_str(), &top_up_amount).await {
+                Ok(tx_hash) => println!("Topped up batch in transaction {}", tx_hash),
                 Err(e) => eprintln!("Error topping up batch: {}", e),
             }
 
             // Dilute the batch
             let new_depth = 21;
-            match client.dilute_batch(&batch_id, new_depth).await {
-                Ok(new_batch_id) => println!("Diluted batch, new ID: {}", new_batch_id),
+            match client.dilute_batch(batch_id.as_str(), new_depth).await {
+                Ok(tx_hash) => println!("Diluted batch in transaction {}", tx_hash),
                 Err(e) => eprintln!("Error diluting batch: {}", e),
             }
         }
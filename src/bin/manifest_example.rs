@@ -0,0 +1,42 @@
+use bee_rs::api::manifest::{Manifest, CONTENT_TYPE_KEY, FILENAME_KEY};
+use std::collections::BTreeMap;
+
+const BEE_API_URL: &str = "http://localhost:1633";
+
+#[tokio::main]
+async fn main() {
+    let client = reqwest::Client::new();
+    let manifest_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f"; // Replace with a valid collection reference
+    let file_reference = "1000000000000000000000000000000000000000000000000000000000000000"; // Replace with a valid file reference
+    let postage_batch_id = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f"; // Replace with a valid batch ID
+
+    let mut manifest = match Manifest::load(&client, BEE_API_URL, manifest_reference).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Error loading manifest: {}", e);
+            return;
+        }
+    };
+
+    // Example: Replace a single file of the collection
+    let metadata = BTreeMap::from([
+        (CONTENT_TYPE_KEY.to_string(), "text/html".to_string()),
+        (FILENAME_KEY.to_string(), "about.html".to_string()),
+    ]);
+    if let Err(e) = manifest.add_file("about.html", file_reference, metadata).await {
+        eprintln!("Error adding file: {}", e);
+    }
+
+    // Example: Move a file and change the index document
+    if let Err(e) = manifest.rename("old/page.html", "new/page.html").await {
+        eprintln!("Error renaming file: {}", e);
+    }
+    if let Err(e) = manifest.set_index_document("about.html").await {
+        eprintln!("Error setting index document: {}", e);
+    }
+
+    match manifest.save(postage_batch_id, None).await {
+        Ok(reference) => println!("New manifest reference: {}", reference),
+        Err(e) => eprintln!("Error saving manifest: {}", e),
+    }
+}
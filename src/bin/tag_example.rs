@@ -0,0 +1,45 @@
+use bee_rs::api::bytes::{upload, RedundantUploadOptions, UploadOptions};
+use bee_rs::api::tag::{create_tag, delete_tag, wait_for_sync};
+use std::time::Duration;
+
+const BEE_API_URL: &str = "http://localhost:1633";
+
+#[tokio::main]
+async fn main() {
+    let client = reqwest::Client::new();
+    let postage_batch_id = "f1e4ff753ea1cb923269ed0cda909d13a10d624719edf261e196584e9e764e50"; // Replace with a valid batch ID
+
+    // Example: Create a tag for an upload
+    let tag = match create_tag(&client, BEE_API_URL).await {
+        Ok(tag) => tag,
+        Err(e) => {
+            eprintln!("Error creating tag: {}", e);
+            return;
+        }
+    };
+    println!("Created tag: {:#?}", tag);
+
+    // Example: Upload data with the tag
+    let options = Some(RedundantUploadOptions {
+        upload_options: UploadOptions {
+            tag: Some(tag.uid),
+            ..Default::default()
+        },
+        redundancy_level: None,
+    });
+    match upload(&client, BEE_API_URL, b"Hello, Swarm!".to_vec(), postage_batch_id, options).await {
+        Ok(result) => println!("Uploaded: {}", result.reference),
+        Err(e) => eprintln!("Error uploading data: {}", e),
+    }
+
+    // Example: Wait until the upload is synced
+    match wait_for_sync(&client, BEE_API_URL, tag.uid, Duration::from_secs(60)).await {
+        Ok(tag) => println!("Synced {} chunks", tag.synced),
+        Err(e) => eprintln!("Error waiting for sync: {}", e),
+    }
+
+    // Example: Delete the tag
+    if let Err(e) = delete_tag(&client, BEE_API_URL, tag.uid).await {
+        eprintln!("Error deleting tag: {}", e);
+    }
+}
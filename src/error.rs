@@ -18,6 +18,9 @@ pub enum Error {
     /// Status code error
     #[error("Status code error: {0}")]
     StatusCode(reqwest::StatusCode),
+    /// Not found error
+    #[error("Not found: {0}")]
+    NotFound(String),
     /// Custom error
     #[error("{0}")]
     Custom(String),
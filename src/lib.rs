@@ -1,4 +1,5 @@
 pub mod api;
 pub mod bee;
 pub mod error;
+pub mod utils;
 
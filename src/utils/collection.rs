@@ -0,0 +1,130 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for collections of files.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/collection.ts
+
+use crate::api::bzz::CollectionEntry;
+
+/// The document used as index document when a collection contains it.
+pub const DEFAULT_INDEX_DOCUMENT: &str = "index.html";
+
+/// The total size of the files of a collection.
+pub fn collection_size(entries: &[CollectionEntry]) -> u64 {
+    entries.iter().map(|entry| entry.size).sum()
+}
+
+/// The index document to use for a collection: `index.html` at its root,
+/// if present.
+pub fn detect_index_document(entries: &[CollectionEntry]) -> Option<String> {
+    entries
+        .iter()
+        .find(|entry| entry.path == DEFAULT_INDEX_DOCUMENT)
+        .map(|entry| entry.path.clone())
+}
+
+/// Rules in `.gitignore` format selecting paths to leave out of a collection.
+///
+/// Supported are `*`, `?` and `**` wildcards, `!` negation, a trailing `/`
+/// matching directories only and a `/` inside a pattern anchoring it to the
+/// collection root. Patterns without a `/` match the name of a file or
+/// directory at any depth.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    patterns: Vec<IgnorePattern>,
+}
+
+#[derive(Debug, Clone)]
+struct IgnorePattern {
+    glob: String,
+    negated: bool,
+    directory_only: bool,
+    anchored: bool,
+}
+
+impl IgnoreRules {
+    /// Create rules from a list of patterns.
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns = patterns
+            .into_iter()
+            .filter_map(|pattern| IgnorePattern::parse(pattern.as_ref()))
+            .collect();
+        Self { patterns }
+    }
+
+    /// Parse rules from the content of an ignore file. Empty lines and lines
+    /// starting with `#` are skipped.
+    pub fn parse(content: &str) -> Self {
+        Self::new(content.lines())
+    }
+
+    /// Whether the path, relative to the collection root, is ignored.
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let mut ignored = false;
+        for pattern in &self.patterns {
+            if pattern.directory_only && !is_dir {
+                continue;
+            }
+            let target = if pattern.anchored { path } else { name };
+            if glob_match(pattern.glob.as_bytes(), target.as_bytes()) {
+                ignored = !pattern.negated;
+            }
+        }
+        ignored
+    }
+}
+
+impl IgnorePattern {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (directory_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let glob = line.trim_start_matches('/');
+        if glob.is_empty() {
+            return None;
+        }
+        Some(Self {
+            glob: glob.to_string(),
+            negated,
+            directory_only,
+            anchored,
+        })
+    }
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            glob_match(rest, text)
+                || text
+                    .iter()
+                    .enumerate()
+                    .any(|(i, c)| *c == b'/' && glob_match(rest, &text[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => {
+            let limit = text.iter().position(|c| *c == b'/').unwrap_or(text.len());
+            (0..=limit).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'?', rest @ ..] => {
+            matches!(text.first(), Some(c) if *c != b'/') && glob_match(rest, &text[1..])
+        }
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Collections read from the file system.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/collection.node.ts
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::api::bzz::{CollectionEntry, CollectionSource};
+use crate::utils::collection::IgnoreRules;
+
+/// Build a collection from the files below `dir`.
+///
+/// Entries are ordered by path and use `/` as separator on every platform.
+/// Empty directories are not part of a collection. Symbolic links are
+/// skipped unless `follow_symlinks` is set, in which case they are included
+/// with the content of their target; dangling links and links back to a
+/// parent directory are skipped.
+pub fn make_collection_from_fs(
+    dir: &Path,
+    ignore: &IgnoreRules,
+    follow_symlinks: bool,
+) -> io::Result<Vec<CollectionEntry>> {
+    let mut entries = Vec::new();
+    let mut ancestors = HashSet::from([fs::canonicalize(dir)?]);
+    walk(dir, "", ignore, follow_symlinks, &mut ancestors, &mut entries)?;
+    Ok(entries)
+}
+
+fn walk(
+    dir: &Path,
+    prefix: &str,
+    ignore: &IgnoreRules,
+    follow_symlinks: bool,
+    ancestors: &mut HashSet<PathBuf>,
+    entries: &mut Vec<CollectionEntry>,
+) -> io::Result<()> {
+    let mut children = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    children.sort_by_key(|child| child.file_name());
+
+    for child in children {
+        let name = child.file_name().into_string().map_err(|name| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("file name is not valid UTF-8: {:?}", name),
+            )
+        })?;
+        let path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+
+        let metadata = if child.file_type()?.is_symlink() {
+            if !follow_symlinks {
+                continue;
+            }
+            match fs::metadata(child.path()) {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            }
+        } else {
+            child.metadata()?
+        };
+
+        if metadata.is_dir() {
+            if ignore.is_ignored(&path, true) {
+                continue;
+            }
+            let canonical = fs::canonicalize(child.path())?;
+            if !ancestors.insert(canonical.clone()) {
+                continue;
+            }
+            walk(&child.path(), &path, ignore, follow_symlinks, ancestors, entries)?;
+            ancestors.remove(&canonical);
+        } else if metadata.is_file() && !ignore.is_ignored(&path, false) {
+            entries.push(CollectionEntry {
+                path,
+                size: metadata.len(),
+                source: CollectionSource::File(child.path()),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Human readable durations and block counts.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/duration.ts
+//!
+//! Durations are written as a sequence of amounts with a unit, such as
+//! `"1d 6h"`, `"3 weeks"` or `"1.5h"`.
+
+use std::time::Duration;
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::error::Error;
+use crate::utils::fixed_point_number::FixedPointNumber;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// The decimals accepted in an amount, enough for nanoseconds of a second.
+const AMOUNT_DIGITS: u32 = 9;
+
+/// The units accepted by [`parse_duration`], in nanoseconds.
+const UNITS: [(&[&str], u128); 7] = [
+    (&["ns", "nanosecond", "nanoseconds"], 1),
+    (&["ms", "millisecond", "milliseconds"], 1_000_000),
+    (&["s", "sec", "secs", "second", "seconds"], NANOS_PER_SECOND),
+    (&["m", "min", "mins", "minute", "minutes"], 60 * NANOS_PER_SECOND),
+    (&["h", "hr", "hrs", "hour", "hours"], 60 * 60 * NANOS_PER_SECOND),
+    (&["d", "day", "days"], 24 * 60 * 60 * NANOS_PER_SECOND),
+    (&["w", "week", "weeks"], 7 * 24 * 60 * 60 * NANOS_PER_SECOND),
+];
+
+/// The units used by [`format_duration`], in seconds.
+const FORMAT_UNITS: [(&str, u64); 4] = [("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60), ("s", 1)];
+
+/// Parse a duration such as `"1d 6h"`, `"90 minutes"` or `"1.5h"`.
+///
+/// Amounts may have decimals and are summed up. Every amount needs a unit.
+pub fn parse_duration(s: &str) -> Result<Duration, Error> {
+    let invalid = |reason: &str| Error::Custom(format!("invalid duration {:?}: {}", s, reason));
+
+    let mut nanos: u128 = 0;
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        return Err(invalid("empty"));
+    }
+    while !rest.is_empty() {
+        let amount_end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+        let (amount, tail) = rest.split_at(amount_end);
+        let tail = tail.trim_start();
+        let unit_end = tail.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_end);
+
+        if amount.is_empty() {
+            return Err(invalid("expected an amount"));
+        }
+        if unit.is_empty() {
+            return Err(invalid(&format!("missing unit after {}", amount)));
+        }
+        let unit_nanos = UNITS
+            .iter()
+            .find(|(names, _)| names.contains(&unit.to_ascii_lowercase().as_str()))
+            .map(|(_, unit_nanos)| *unit_nanos)
+            .ok_or_else(|| invalid(&format!("unknown unit {:?}", unit)))?;
+
+        let amount = FixedPointNumber::from_decimal_str(amount, AMOUNT_DIGITS)
+            .map_err(|_| invalid(&format!("invalid amount {:?}", amount)))?;
+        let amount_nanos = (amount.value() * BigInt::from(unit_nanos) / BigInt::from(NANOS_PER_SECOND))
+            .to_u128()
+            .ok_or_else(|| invalid("too long"))?;
+        nanos = nanos.checked_add(amount_nanos).ok_or_else(|| invalid("too long"))?;
+
+        rest = tail.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+
+    let seconds = u64::try_from(nanos / NANOS_PER_SECOND).map_err(|_| invalid("too long"))?;
+    Ok(Duration::new(seconds, (nanos % NANOS_PER_SECOND) as u32))
+}
+
+/// Format a duration as days, hours, minutes and seconds, e.g. `"1d 6h"`.
+///
+/// Fractions of a second are dropped, unless the duration is shorter than a
+/// second.
+pub fn format_duration(duration: Duration) -> String {
+    let mut seconds = duration.as_secs();
+    if seconds == 0 {
+        return match duration.subsec_millis() {
+            0 => "0s".to_string(),
+            millis => format!("{}ms", millis),
+        };
+    }
+
+    let mut parts = Vec::new();
+    for (unit, unit_seconds) in FORMAT_UNITS {
+        let amount = seconds / unit_seconds;
+        if amount > 0 {
+            parts.push(format!("{}{}", amount, unit));
+            seconds %= unit_seconds;
+        }
+    }
+    parts.join(" ")
+}
+
+/// The time `blocks` blocks take to be mined, e.g. with `stamps::BLOCK_TIME`.
+pub fn blocks_to_duration(blocks: u64, block_time: Duration) -> Duration {
+    let nanos = u128::from(blocks).saturating_mul(block_time.as_nanos());
+    match u64::try_from(nanos / NANOS_PER_SECOND) {
+        Ok(seconds) => Duration::new(seconds, (nanos % NANOS_PER_SECOND) as u32),
+        Err(_) => Duration::MAX,
+    }
+}
+
+/// The number of blocks mined during `duration`.
+///
+/// A started block counts as a whole one, as a batch pays for every block it
+/// is alive in.
+pub fn duration_to_blocks(duration: Duration, block_time: Duration) -> Result<u64, Error> {
+    if block_time.is_zero() {
+        return Err(Error::Custom("block time must not be zero".to_string()));
+    }
+    let blocks = duration.as_nanos().div_ceil(block_time.as_nanos());
+    Ok(blocks.try_into().unwrap_or(u64::MAX))
+}
@@ -0,0 +1,91 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decimal numbers stored as an integer of their smallest unit.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/tokens.ts
+//!
+//! A number with `digits` decimals is stored as `value / 10^digits`, so
+//! `1.5` with 16 digits is stored as `15000000000000000`.
+
+use std::fmt;
+
+use num_bigint::BigInt;
+use num_traits::Signed;
+
+use crate::error::Error;
+
+/// A decimal number with a fixed number of decimals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedPointNumber {
+    value: BigInt,
+    digits: u32,
+}
+
+impl FixedPointNumber {
+    /// Create a number from its value in the smallest unit.
+    pub fn new(value: impl Into<BigInt>, digits: u32) -> Self {
+        Self {
+            value: value.into(),
+            digits,
+        }
+    }
+
+    /// Parse a decimal string such as `"-12.345"`.
+    ///
+    /// Fails if the string has more decimals than `digits`.
+    pub fn from_decimal_str(s: &str, digits: u32) -> Result<Self, Error> {
+        let invalid = || Error::Custom(format!("invalid decimal number: {:?}", s));
+
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+        if !integer.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if fraction.len() > digits as usize {
+            return Err(Error::Custom(format!("{:?} has more than {} decimals", s, digits)));
+        }
+
+        let padded = format!("{}{:0<width$}", integer, fraction, width = digits as usize);
+        let mut value: BigInt = padded.parse().map_err(|_| invalid())?;
+        if negative {
+            value = -value;
+        }
+        Ok(Self { value, digits })
+    }
+
+    /// The value in the smallest unit.
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    /// The number of decimals.
+    pub fn digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// Format the number without trailing zeros, e.g. `"-12.345"` or `"3"`.
+    pub fn to_decimal_string(&self) -> String {
+        let magnitude = format!("{:0>width$}", self.value.abs(), width = self.digits as usize + 1);
+        let (integer, fraction) = magnitude.split_at(magnitude.len() - self.digits as usize);
+        let fraction = fraction.trim_end_matches('0');
+
+        let sign = if self.value.is_negative() { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{}{}", sign, integer)
+        } else {
+            format!("{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
+
+impl fmt::Display for FixedPointNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_decimal_string())
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline calculation of Swarm file references.
+//! https://github.com/ethersphere/bee/blob/master/pkg/file/pipeline/hashtrie/hashtrie.go
+//!
+//! Content is split into 4096 byte chunks, whose addresses are packed 128 at
+//! a time into intermediate chunks until a single root chunk remains. A level
+//! ending with a single reference carries it to the level above instead of
+//! wrapping it into a chunk of its own, as Bee does.
+
+use std::io;
+use std::path::Path;
+
+use tokio::io::AsyncReadExt;
+
+use crate::api::bmt::calculate_chunk_address;
+
+/// The maximum payload size of a chunk.
+pub const CHUNK_PAYLOAD_SIZE: usize = 4096;
+/// The number of references that fit into an intermediate chunk.
+pub const BRANCHES: usize = 128;
+
+const SPAN_SIZE: usize = 8;
+const REFERENCE_SIZE: usize = 32;
+
+/// A chunk reference together with the size of the content below it.
+#[derive(Debug, Clone, Copy)]
+struct ChunkRef {
+    span: u64,
+    address: [u8; REFERENCE_SIZE],
+}
+
+/// The result of hashing content with a [`FileHasher`].
+#[derive(Debug, Clone)]
+pub struct FileTree {
+    /// The reference of the content.
+    pub address: [u8; REFERENCE_SIZE],
+    /// The size of the content in bytes.
+    pub span: u64,
+    /// The address of every chunk of the tree, if they were recorded.
+    pub chunk_addresses: Vec<[u8; REFERENCE_SIZE]>,
+}
+
+/// Incrementally computes the Swarm reference of content uploaded without
+/// encryption through `/bytes` or as a file of a collection.
+#[derive(Debug, Default)]
+pub struct FileHasher {
+    buffer: Vec<u8>,
+    levels: Vec<Vec<ChunkRef>>,
+    chunk_addresses: Option<Vec<[u8; REFERENCE_SIZE]>>,
+}
+
+impl FileHasher {
+    /// Create a new hasher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a hasher that also records the address of every chunk.
+    pub fn recording_chunks() -> Self {
+        Self {
+            chunk_addresses: Some(Vec::new()),
+            ..Self::default()
+        }
+    }
+
+    /// Add content to the hasher.
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let take = (CHUNK_PAYLOAD_SIZE - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() == CHUNK_PAYLOAD_SIZE {
+                self.write_leaf();
+            }
+        }
+    }
+
+    /// Finish hashing and return the reference of the content.
+    pub fn finalize(mut self) -> FileTree {
+        if !self.buffer.is_empty() || self.levels.is_empty() {
+            self.write_leaf();
+        }
+
+        let mut level = 0;
+        let root = loop {
+            let refs = std::mem::take(&mut self.levels[level]);
+            let is_top = self.levels[level + 1..].iter().all(Vec::is_empty);
+            if is_top && refs.len() == 1 {
+                break refs[0];
+            }
+            match refs.len() {
+                0 => {}
+                1 => self.push(level + 1, refs[0]),
+                _ => {
+                    let parent = self.wrap(&refs);
+                    self.push(level + 1, parent);
+                }
+            }
+            level += 1;
+        };
+
+        FileTree {
+            address: root.address,
+            span: root.span,
+            chunk_addresses: self.chunk_addresses.unwrap_or_default(),
+        }
+    }
+
+    fn write_leaf(&mut self) {
+        let payload = std::mem::take(&mut self.buffer);
+        let leaf = self.hash_chunk(payload.len() as u64, &payload);
+        self.push(0, leaf);
+    }
+
+    fn push(&mut self, level: usize, chunk: ChunkRef) {
+        if self.levels.len() <= level {
+            self.levels.resize(level + 1, Vec::new());
+        }
+        self.levels[level].push(chunk);
+        if self.levels[level].len() == BRANCHES {
+            let refs = std::mem::take(&mut self.levels[level]);
+            let parent = self.wrap(&refs);
+            self.push(level + 1, parent);
+        }
+    }
+
+    fn wrap(&mut self, refs: &[ChunkRef]) -> ChunkRef {
+        let span = refs.iter().map(|chunk| chunk.span).sum();
+        let payload: Vec<u8> = refs.iter().flat_map(|chunk| chunk.address).collect();
+        self.hash_chunk(span, &payload)
+    }
+
+    fn hash_chunk(&mut self, span: u64, payload: &[u8]) -> ChunkRef {
+        let mut content = Vec::with_capacity(SPAN_SIZE + payload.len());
+        content.extend_from_slice(&span.to_le_bytes());
+        content.extend_from_slice(payload);
+        let address = calculate_chunk_address(&content).expect("payload fits into a chunk");
+        if let Some(chunk_addresses) = &mut self.chunk_addresses {
+            chunk_addresses.push(address);
+        }
+        ChunkRef { span, address }
+    }
+}
+
+/// Calculate the Swarm reference of `data`.
+pub fn file_address(data: &[u8]) -> [u8; REFERENCE_SIZE] {
+    let mut hasher = FileHasher::new();
+    hasher.update(data);
+    hasher.finalize().address
+}
+
+/// Calculate the Swarm reference of the file at `path` without loading it
+/// into memory.
+pub async fn file_address_from_path(path: impl AsRef<Path>) -> io::Result<[u8; REFERENCE_SIZE]> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = FileHasher::new();
+    let mut buffer = vec![0u8; 16 * CHUNK_PAYLOAD_SIZE];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().address)
+}
@@ -0,0 +1,13 @@
+pub mod collection;
+pub mod collection_node;
+pub mod duration;
+pub mod fixed_point_number;
+pub mod merkle_tree;
+pub mod mime;
+pub mod size;
+pub mod stamps;
+pub mod stream;
+pub mod tar;
+pub mod tar_uploader;
+pub mod tar_writer;
+pub mod tokens;
@@ -0,0 +1,117 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Human readable data sizes.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/size.ts
+//!
+//! Sizes accept decimal units (`kB`, `MB`, `GB`, ...) as powers of 1000 and
+//! binary units (`KiB`, `MiB`, `GiB`, ...) as powers of 1024, case
+//! insensitively.
+
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::error::Error;
+use crate::utils::fixed_point_number::FixedPointNumber;
+use crate::utils::stamps;
+
+/// The decimals accepted in an amount, e.g. `"1.5GB"`.
+const AMOUNT_DIGITS: u32 = 9;
+
+/// The units accepted by [`Size::from_str`], in bytes.
+const UNITS: [(&str, u64); 11] = [
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("pb", 1_000_000_000_000_000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+    ("pib", 1 << 50),
+];
+
+/// The units used by [`Size`]'s `Display`, from the largest.
+const FORMAT_UNITS: [(&str, u64); 5] = [
+    ("PB", 1_000_000_000_000_000),
+    ("TB", 1_000_000_000_000),
+    ("GB", 1_000_000_000),
+    ("MB", 1_000_000),
+    ("kB", 1_000),
+];
+
+/// An amount of data in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Size(u64);
+
+impl Size {
+    pub fn from_bytes(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.0
+    }
+
+    /// The smallest depth of an immutable batch that typically stores this
+    /// much data, see [`stamps::depth_for_volume`].
+    pub fn depth(&self) -> Result<u8, Error> {
+        stamps::depth_for_volume(self.0)
+    }
+
+    /// The data an immutable batch of `depth` typically stores, see
+    /// [`stamps::effective_volume`].
+    pub fn from_depth(depth: u8) -> Result<Self, Error> {
+        stamps::effective_volume(depth).map(Self)
+    }
+}
+
+impl FromStr for Size {
+    type Err = Error;
+
+    /// Parse a size such as `"5GB"`, `"512 MiB"` or `"1.5tb"`. A number
+    /// without unit is in bytes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::Custom(format!("invalid size {:?}: {}", s, reason));
+
+        let s = s.trim();
+        let amount_end = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+        let (amount, unit) = s.split_at(amount_end);
+        let unit = unit.trim_start().to_ascii_lowercase();
+
+        let unit_bytes = if unit.is_empty() {
+            1
+        } else {
+            UNITS
+                .iter()
+                .find(|(name, _)| *name == unit)
+                .map(|(_, unit_bytes)| *unit_bytes)
+                .ok_or_else(|| invalid(&format!("unknown unit {:?}", unit)))?
+        };
+
+        let amount = FixedPointNumber::from_decimal_str(amount, AMOUNT_DIGITS)
+            .map_err(|_| invalid(&format!("invalid amount {:?}", amount)))?;
+        let bytes = amount.value() * BigInt::from(unit_bytes) / BigInt::from(10u64.pow(AMOUNT_DIGITS));
+        bytes.to_u64().map(Self).ok_or_else(|| invalid("too large"))
+    }
+}
+
+impl fmt::Display for Size {
+    /// Format the size in the largest decimal unit it reaches, with up to two
+    /// decimals, e.g. `"5 GB"` or `"536.87 MB"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match FORMAT_UNITS.iter().find(|(_, unit_bytes)| self.0 >= *unit_bytes) {
+            Some((unit, unit_bytes)) => {
+                let hundredths = u128::from(self.0) * 100 / u128::from(*unit_bytes);
+                let amount = FixedPointNumber::new(hundredths, 2);
+                write!(f, "{} {}", amount, unit)
+            }
+            None => write!(f, "{} B", self.0),
+        }
+    }
+}
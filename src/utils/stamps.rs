@@ -0,0 +1,279 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Postage batch utilities.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/stamps.ts
+//!
+//! Every chunk stamped with a batch lands in the collision bucket picked by
+//! the first `bucket_depth` bits of its address. A bucket holds at most
+//! `2^(depth - bucket_depth)` chunks, so a batch is full as soon as a single
+//! bucket is, no matter how empty the others are.
+//!
+//! A batch pays an `amount` of PLUR per chunk, of which the current storage price
+//! is deducted every block, so `amount / price` blocks is its time to live.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive};
+
+use crate::api::debug::stamps::PostageBatchBuckets;
+use crate::error::Error;
+use crate::utils::duration::{blocks_to_duration, duration_to_blocks};
+use crate::utils::size::Size;
+use crate::utils::tokens::BZZ;
+
+/// The size of a chunk in bytes.
+const CHUNK_SIZE: u64 = 4096;
+
+/// The block time of the Gnosis chain, on which postage batches are bought.
+pub const BLOCK_TIME: Duration = Duration::from_secs(5);
+
+/// The share of the theoretical volume that can be used before the first
+/// bucket is full, by depth, as measured by bee-js for immutable batches.
+const EFFECTIVE_UTILIZATION: [(u8, f64); 13] = [
+    (22, 0.2867),
+    (23, 0.4956),
+    (24, 0.6433),
+    (25, 0.7478),
+    (26, 0.8217),
+    (27, 0.8739),
+    (28, 0.9108),
+    (29, 0.9369),
+    (30, 0.9554),
+    (31, 0.9685),
+    (32, 0.9777),
+    (33, 0.9842),
+    (34, 0.9889),
+];
+/// The effective utilization of batches deeper than the table.
+const DEEP_UTILIZATION: f64 = 0.99;
+/// Batches shallower than the table are too small to be worth using.
+const MIN_EFFECTIVE_DEPTH: u8 = 22;
+/// The deepest batch whose volume in bytes fits into a `u64`.
+const MAX_EFFECTIVE_DEPTH: u8 = 51;
+
+/// The fill of a batch in percent, given its `utilization`, the number of
+/// chunks in its fullest bucket.
+pub fn utilization_percent(utilization: u32, depth: u8, bucket_depth: u8) -> f64 {
+    let capacity = 2f64.powi(i32::from(depth) - i32::from(bucket_depth));
+    f64::from(utilization) / capacity * 100.0
+}
+
+/// The number of bytes a batch of `depth` could store if its buckets filled
+/// evenly.
+///
+/// Fails for batches deeper than 51, whose volume does not fit into a `u64`.
+pub fn theoretical_volume(depth: u8) -> Result<u64, Error> {
+    if depth > MAX_EFFECTIVE_DEPTH {
+        return Err(Error::Custom(format!("batch depth {} is too deep", depth)));
+    }
+    Ok(CHUNK_SIZE << depth)
+}
+
+/// The number of bytes an immutable batch of `depth` typically stores before
+/// its fullest bucket overflows.
+///
+/// Returns 0 for batches shallower than 22, which fill up too unevenly, and
+/// fails for batches deeper than 51.
+pub fn effective_volume(depth: u8) -> Result<u64, Error> {
+    let volume = theoretical_volume(depth)?;
+    if depth < MIN_EFFECTIVE_DEPTH {
+        return Ok(0);
+    }
+    let rate = EFFECTIVE_UTILIZATION
+        .iter()
+        .find(|(table_depth, _)| *table_depth == depth)
+        .map_or(DEEP_UTILIZATION, |(_, rate)| *rate);
+    Ok((volume as f64 * rate) as u64)
+}
+
+/// The smallest depth of an immutable batch that typically stores `bytes`.
+pub fn depth_for_volume(bytes: u64) -> Result<u8, Error> {
+    (MIN_EFFECTIVE_DEPTH..=MAX_EFFECTIVE_DEPTH)
+        .find(|depth| effective_volume(*depth).is_ok_and(|volume| volume >= bytes))
+        .ok_or_else(|| Error::Custom(format!("no batch is deep enough to store {} bytes", bytes)))
+}
+
+/// The total cost of a batch of `depth` paying `amount` per chunk.
+pub fn stamp_cost(depth: u8, amount: &BZZ) -> BZZ {
+    BZZ::from_plur(amount.to_plur() << depth)
+}
+
+/// The time to live of a batch with `amount` per chunk left, at the current
+/// `price` per chunk and block, e.g. `ChainState::current_price`.
+pub fn batch_ttl(amount: &BZZ, price: &BZZ, block_time: Duration) -> Result<Duration, Error> {
+    if !price.to_plur().is_positive() {
+        return Err(Error::Custom(format!("storage price must be positive, got {}", price)));
+    }
+    if amount.is_negative() {
+        return Ok(Duration::ZERO);
+    }
+    let blocks = (amount.to_plur() / price.to_plur()).to_u64().unwrap_or(u64::MAX);
+    Ok(blocks_to_duration(blocks, block_time))
+}
+
+/// The amount per chunk that keeps a batch alive for `ttl` at the current
+/// `price` per chunk and block.
+///
+/// This is both the amount to buy a batch with and the amount to top up a
+/// batch with to extend its time to live by `ttl`.
+pub fn amount_for_ttl(ttl: Duration, price: &BZZ, block_time: Duration) -> Result<BZZ, Error> {
+    let blocks = duration_to_blocks(ttl, block_time)?;
+    Ok(BZZ::from_plur(price.to_plur() * BigInt::from(blocks)))
+}
+
+/// What buying a batch for some data and time to live takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchEstimate {
+    pub depth: u8,
+    /// The amount per chunk to buy the batch with.
+    pub amount: BZZ,
+    /// The total cost of the batch.
+    pub cost: BZZ,
+}
+
+/// Estimate the batch that stores `size` for `ttl` at the current `price` per
+/// chunk and block, e.g. `"5GB".parse()?` for three weeks.
+pub fn estimate_batch(size: Size, ttl: Duration, price: &BZZ, block_time: Duration) -> Result<BatchEstimate, Error> {
+    let depth = size.depth()?;
+    let amount = amount_for_ttl(ttl, price, block_time)?;
+    let cost = stamp_cost(depth, &amount);
+    Ok(BatchEstimate { depth, amount, cost })
+}
+
+/// A bucket that would hold more chunks than the batch allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BucketOverflow {
+    pub bucket: u32,
+    /// The number of chunks the bucket would hold.
+    pub collisions: u32,
+    pub capacity: u32,
+}
+
+/// Predicts the bucket utilization of a batch as chunks are added to it.
+#[derive(Debug, Clone)]
+pub struct BucketSimulation {
+    depth: u8,
+    bucket_depth: u8,
+    buckets: Vec<u32>,
+}
+
+impl BucketSimulation {
+    /// Simulate an empty batch.
+    pub fn new(depth: u8, bucket_depth: u8) -> Result<Self, Error> {
+        if bucket_depth == 0 || bucket_depth > 24 || depth < bucket_depth || depth - bucket_depth > 31 {
+            return Err(Error::Custom(format!(
+                "invalid batch depth {} with bucket depth {}",
+                depth, bucket_depth
+            )));
+        }
+        Ok(Self {
+            depth,
+            bucket_depth,
+            buckets: vec![0; 1 << bucket_depth],
+        })
+    }
+
+    /// Simulate a batch starting from its current collisions as returned by
+    /// `BeeDebugStampsClient::get_postage_batch_buckets`.
+    pub fn from_buckets(buckets: &PostageBatchBuckets) -> Result<Self, Error> {
+        let mut simulation = Self::new(buckets.depth, buckets.bucket_depth)?;
+        for bucket in &buckets.buckets {
+            let count = simulation
+                .buckets
+                .get_mut(bucket.bucket_id as usize)
+                .ok_or_else(|| Error::Custom(format!("invalid bucket ID: {}", bucket.bucket_id)))?;
+            *count = bucket.collisions;
+        }
+        Ok(simulation)
+    }
+
+    /// The depth of the batch.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The number of address bits selecting a bucket.
+    pub fn bucket_depth(&self) -> u8 {
+        self.bucket_depth
+    }
+
+    /// The number of chunks a bucket can hold.
+    pub fn bucket_capacity(&self) -> u32 {
+        1 << (self.depth - self.bucket_depth)
+    }
+
+    /// The number of chunks in every bucket, indexed by bucket.
+    pub fn buckets(&self) -> &[u32] {
+        &self.buckets
+    }
+
+    /// The bucket of a chunk address.
+    pub fn bucket_of(&self, chunk_address: &[u8; 32]) -> u32 {
+        let prefix = u32::from_be_bytes(chunk_address[..4].try_into().unwrap());
+        prefix >> (32 - u32::from(self.bucket_depth))
+    }
+
+    /// Add a chunk to its bucket.
+    pub fn add(&mut self, chunk_address: &[u8; 32]) {
+        let bucket = self.bucket_of(chunk_address) as usize;
+        self.buckets[bucket] += 1;
+    }
+
+    /// Add every chunk of an upload, e.g. the chunk addresses recorded by
+    /// `FileHasher::recording_chunks`.
+    ///
+    /// Repeated addresses are counted once, as Bee stamps identical chunks
+    /// only once.
+    pub fn add_all<'a>(&mut self, chunk_addresses: impl IntoIterator<Item = &'a [u8; 32]>) {
+        let mut seen = HashSet::new();
+        for chunk_address in chunk_addresses {
+            if seen.insert(chunk_address) {
+                self.add(chunk_address);
+            }
+        }
+    }
+
+    /// The number of chunks in the fullest bucket.
+    pub fn max_collisions(&self) -> u32 {
+        self.buckets.iter().copied().max().unwrap_or_default()
+    }
+
+    /// The buckets holding more chunks than the batch allows.
+    pub fn overflows(&self) -> Vec<BucketOverflow> {
+        let capacity = self.bucket_capacity();
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, &collisions)| collisions > capacity)
+            .map(|(bucket, &collisions)| BucketOverflow {
+                bucket: bucket as u32,
+                collisions,
+                capacity,
+            })
+            .collect()
+    }
+
+    /// Whether every chunk fits into the batch.
+    ///
+    /// An immutable batch rejects the upload otherwise, a mutable one
+    /// overwrites the oldest chunks of the overflowing buckets.
+    pub fn fits(&self) -> bool {
+        self.max_collisions() <= self.bucket_capacity()
+    }
+}
+
+/// Predict the bucket utilization of a batch after uploading the given
+/// chunks.
+///
+/// Check [`BucketSimulation::overflows`] for buckets that would not fit.
+pub fn simulate_upload<'a>(
+    buckets: &PostageBatchBuckets,
+    chunk_addresses: impl IntoIterator<Item = &'a [u8; 32]>,
+) -> Result<BucketSimulation, Error> {
+    let mut simulation = BucketSimulation::from_buckets(buckets)?;
+    simulation.add_all(chunk_addresses);
+    Ok(simulation)
+}
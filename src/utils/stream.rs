@@ -0,0 +1,45 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Request bodies streamed from readers.
+
+use std::io;
+
+use bytes::Bytes;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const READ_BUFFER_SIZE: u64 = 64 * 1024;
+
+/// Stream the content of `reader` in buffers of up to 64 KiB.
+///
+/// With a `size`, exactly that many bytes are read and a reader ending
+/// early is an error. Otherwise the reader is read until its end.
+pub fn reader_stream(
+    reader: impl AsyncRead + Send + 'static,
+    size: Option<u64>,
+) -> BoxStream<'static, io::Result<Bytes>> {
+    let reader = Box::pin(reader);
+    stream::try_unfold((reader, size), |(mut reader, remaining)| async move {
+        if remaining == Some(0) {
+            return Ok(None);
+        }
+        let capacity = remaining.map_or(READ_BUFFER_SIZE, |remaining| remaining.min(READ_BUFFER_SIZE));
+        let mut buffer = vec![0u8; capacity as usize];
+        let read = reader.read(&mut buffer).await?;
+        if read == 0 {
+            return match remaining {
+                Some(remaining) => Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("content ended {} bytes before its declared size", remaining),
+                )),
+                None => Ok(None),
+            };
+        }
+        buffer.truncate(read);
+        let remaining = remaining.map(|remaining| remaining - read as u64);
+        Ok(Some((Bytes::from(buffer), (reader, remaining))))
+    })
+    .boxed()
+}
@@ -0,0 +1,68 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tar archive blocks used for collection uploads.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/tar.ts
+
+use std::io;
+
+use tar::{EntryType, Header};
+
+/// The size of a tar block.
+pub const BLOCK_SIZE: u64 = 512;
+
+const GNU_LONG_NAME: &[u8] = b"././@LongLink";
+const NAME_SIZE: usize = 100;
+
+/// Build the header blocks of a regular file entry.
+///
+/// Paths that do not fit into the header are preceded by a GNU long name
+/// entry, as done by `tar::Builder`.
+pub fn file_header(path: &str, size: u64) -> io::Result<Vec<u8>> {
+    let mut header = new_header(size, EntryType::Regular);
+    let mut blocks = Vec::with_capacity(BLOCK_SIZE as usize);
+
+    if let Err(e) = header.set_path(path) {
+        let name = path.as_bytes();
+        if name.len() < NAME_SIZE {
+            return Err(e);
+        }
+
+        let mut long_name = new_header(name.len() as u64 + 1, EntryType::GNULongName);
+        if let Some(gnu) = long_name.as_gnu_mut() {
+            gnu.name[..GNU_LONG_NAME.len()].copy_from_slice(GNU_LONG_NAME);
+        }
+        long_name.set_cksum();
+        blocks.extend_from_slice(long_name.as_bytes());
+        blocks.extend_from_slice(name);
+        blocks.push(0);
+        blocks.resize(blocks.len() + padding(name.len() as u64 + 1), 0);
+
+        header.as_old_mut().name.copy_from_slice(&name[..NAME_SIZE]);
+    }
+
+    header.set_cksum();
+    blocks.extend_from_slice(header.as_bytes());
+    Ok(blocks)
+}
+
+/// The number of zero bytes needed to fill the last block of an entry.
+pub fn padding(size: u64) -> usize {
+    ((BLOCK_SIZE - size % BLOCK_SIZE) % BLOCK_SIZE) as usize
+}
+
+/// The two zero blocks marking the end of an archive.
+pub fn end_of_archive() -> Vec<u8> {
+    vec![0u8; 2 * BLOCK_SIZE as usize]
+}
+
+fn new_header(size: u64, entry_type: EntryType) -> Header {
+    let mut header = Header::new_gnu();
+    header.set_mode(0o644);
+    header.set_uid(0);
+    header.set_gid(0);
+    header.set_mtime(0);
+    header.set_size(size);
+    header.set_entry_type(entry_type);
+    header
+}
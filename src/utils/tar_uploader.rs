@@ -0,0 +1,48 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Upload of collections as tar archives.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/tar-uploader.ts
+
+use reqwest::{Body, Client};
+
+use crate::api::bytes::UploadResult;
+use crate::api::bzz::{CollectionEntry, CollectionUploadOptions};
+use crate::api::headers::{read_upload_result, with_redundant_upload_options};
+use crate::error::Error;
+use crate::utils::tar_writer::write_tar;
+
+const BZZ_ENDPOINT: &str = "bzz";
+
+/// Stream the entries of a collection as a tar archive to `/bzz`.
+pub async fn upload_tar(
+    client: &Client,
+    base_url: &str,
+    entries: Vec<CollectionEntry>,
+    postage_batch_id: &str,
+    options: Option<CollectionUploadOptions>,
+) -> Result<UploadResult, Error> {
+    let url = format!("{}/{}", base_url, BZZ_ENDPOINT);
+    let archive = write_tar(entries)?;
+
+    let mut request_builder = client
+        .post(&url)
+        .header("content-type", "application/x-tar")
+        .header("content-length", archive.size.to_string())
+        .header("swarm-collection", "true")
+        .header("swarm-postage-batch-id", postage_batch_id)
+        .body(Body::wrap_stream(archive.stream));
+
+    if let Some(opts) = options {
+        request_builder = with_redundant_upload_options(request_builder, &opts.redundant_upload_options);
+        if let Some(index_document) = opts.index_document {
+            request_builder = request_builder.header("swarm-index-document", index_document);
+        }
+        if let Some(error_document) = opts.error_document {
+            request_builder = request_builder.header("swarm-error-document", error_document);
+        }
+    }
+
+    let response = request_builder.send().await?;
+    Ok(read_upload_result(response).await?)
+}
@@ -0,0 +1,79 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streaming tar writer for collections.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/tar-writer.ts
+//!
+//! Headers are prepared up front, while file contents are only read when the
+//! stream is polled, so an archive never has to be held in memory.
+
+use std::io;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+
+use crate::api::bzz::{CollectionEntry, CollectionSource};
+use crate::utils::stream::reader_stream;
+use crate::utils::tar;
+
+/// A tar archive being produced as a stream of bytes.
+pub struct TarArchive {
+    /// The total size of the archive in bytes.
+    pub size: u64,
+    /// The content of the archive.
+    pub stream: BoxStream<'static, io::Result<Bytes>>,
+}
+
+/// Create a tar archive containing every entry of the collection.
+pub fn write_tar(entries: Vec<CollectionEntry>) -> io::Result<TarArchive> {
+    let mut size = 0;
+    let mut parts = Vec::with_capacity(entries.len() + 1);
+
+    for entry in entries {
+        if let CollectionSource::Data(data) = &entry.source {
+            if data.len() as u64 != entry.size {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("size of {} does not match its data", entry.path),
+                ));
+            }
+        }
+
+        let header = tar::file_header(&entry.path, entry.size)?;
+        let padding = tar::padding(entry.size);
+        size += header.len() as u64 + entry.size + padding as u64;
+
+        let content = match entry.source {
+            CollectionSource::Data(data) => stream::once(async move { Ok(Bytes::from(data)) }).boxed(),
+            CollectionSource::File(path) => read_file(path, entry.size),
+        };
+        parts.push(
+            stream::once(async move { Ok(Bytes::from(header)) })
+                .chain(content)
+                .chain(stream::once(async move { Ok(Bytes::from(vec![0u8; padding])) }))
+                .boxed(),
+        );
+    }
+
+    let end = tar::end_of_archive();
+    size += end.len() as u64;
+    parts.push(stream::once(async move { Ok(Bytes::from(end)) }).boxed());
+
+    Ok(TarArchive {
+        size,
+        stream: stream::iter(parts)
+            .flatten()
+            .try_filter(|bytes| futures::future::ready(!bytes.is_empty()))
+            .boxed(),
+    })
+}
+
+/// Read exactly `size` bytes of the file at `path`.
+fn read_file(path: PathBuf, size: u64) -> BoxStream<'static, io::Result<Bytes>> {
+    stream::once(async move { tokio::fs::File::open(&path).await })
+        .map_ok(move |file| reader_stream(file, Some(size)))
+        .try_flatten()
+        .boxed()
+}
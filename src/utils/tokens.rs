@@ -0,0 +1,230 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Amounts of the tokens used by Bee.
+//! https://github.com/ethersphere/bee-js/blob/main/src/utils/tokens.ts
+//!
+//! [`BZZ`] pays for storage and bandwidth and has 16 decimals, its smallest
+//! unit being the PLUR. [`DAI`], the native token of the Gnosis chain, pays
+//! for transactions and has 18 decimals, its smallest unit being the wei.
+//! Bee encodes both as decimal strings of their smallest unit.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+use crate::utils::fixed_point_number::FixedPointNumber;
+
+macro_rules! token {
+    ($(#[$meta:meta])* $name:ident, $digits:expr, $unit:ident, $from_unit:ident, $from_unit_str:ident, $to_unit:ident) => {
+        $(#[$meta])*
+        #[allow(clippy::upper_case_acronyms)]
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(BigInt);
+
+        impl $name {
+            /// The number of decimals of the token.
+            pub const DIGITS: u32 = $digits;
+
+            #[doc = concat!("Create an amount of ", stringify!($unit), ".")]
+            pub fn $from_unit(amount: impl Into<BigInt>) -> Self {
+                Self(amount.into())
+            }
+
+            #[doc = concat!("Parse an integer amount of ", stringify!($unit), " as sent by Bee.")]
+            pub fn $from_unit_str(amount: &str) -> Result<Self, Error> {
+                amount
+                    .parse()
+                    .map(Self)
+                    .map_err(|e| Error::Custom(format!("invalid {} amount {:?}: {}", stringify!($unit), amount, e)))
+            }
+
+            #[doc = concat!("Parse a decimal amount of ", stringify!($name), ", e.g. `\"1.5\"`.")]
+            pub fn from_decimal_str(amount: &str) -> Result<Self, Error> {
+                FixedPointNumber::from_decimal_str(amount, Self::DIGITS).map(|number| Self(number.value().clone()))
+            }
+
+            #[doc = concat!("The amount in ", stringify!($unit), ".")]
+            pub fn $to_unit(&self) -> &BigInt {
+                &self.0
+            }
+
+            #[doc = concat!("The amount in ", stringify!($name), " without trailing zeros, e.g. `\"1.5\"`.")]
+            pub fn to_decimal_string(&self) -> String {
+                FixedPointNumber::new(self.0.clone(), Self::DIGITS).to_decimal_string()
+            }
+
+            pub fn zero() -> Self {
+                Self(BigInt::zero())
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.0.is_zero()
+            }
+
+            pub fn is_negative(&self) -> bool {
+                self.0.is_negative()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            #[doc = concat!("Parse a decimal amount of ", stringify!($name), ", optionally followed by the token name.")]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::from_decimal_str(s.strip_suffix(concat!(" ", stringify!($name))).unwrap_or(s))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.to_decimal_string(), stringify!($name))
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Add<&$name> for &$name {
+            type Output = $name;
+
+            fn add(self, other: &$name) -> $name {
+                $name(&self.0 + &other.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl Sub<&$name> for &$name {
+            type Output = $name;
+
+            fn sub(self, other: &$name) -> $name {
+                $name(&self.0 - &other.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<u64> for $name {
+            type Output = Self;
+
+            fn mul(self, factor: u64) -> Self {
+                Self(self.0 * factor)
+            }
+        }
+
+        impl Mul<u64> for &$name {
+            type Output = $name;
+
+            fn mul(self, factor: u64) -> $name {
+                $name(&self.0 * factor)
+            }
+        }
+
+        /// Divides rounding towards zero.
+        impl Div<u64> for $name {
+            type Output = Self;
+
+            fn div(self, divisor: u64) -> Self {
+                Self(self.0 / divisor)
+            }
+        }
+
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::zero(), Add::add)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0.to_string())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(AmountVisitor).map(Self)
+            }
+        }
+    };
+}
+
+token!(
+    /// An amount of BZZ, stored in PLUR.
+    BZZ,
+    16,
+    PLUR,
+    from_plur,
+    from_plur_str,
+    to_plur
+);
+
+token!(
+    /// An amount of xDAI, stored in wei.
+    DAI,
+    18,
+    wei,
+    from_wei,
+    from_wei_str,
+    to_wei
+);
+
+/// Accepts an integer amount of the smallest unit as a string or a number.
+struct AmountVisitor;
+
+impl Visitor<'_> for AmountVisitor {
+    type Value = BigInt;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an integer amount as a string or a number")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<BigInt, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<BigInt, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<BigInt, E> {
+        Ok(value.into())
+    }
+}
@@ -0,0 +1,324 @@
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bee_rs::api::debug::batch_manager::{BatchAction, BatchManager, BatchManagerError, BatchPolicy};
+use bee_rs::api::debug::stamps::BeeDebugStampsError;
+use bee_rs::utils::duration::parse_duration;
+use bee_rs::utils::tokens::BZZ;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const TX_HASH: &str = "0x1234567890123456789012345678901234567890123456789012345678901234";
+const BATCH_ID: &str = "b330000000000000000000000000000000000000000000000000000000000000";
+
+fn batch(utilization: u32, usable: bool, ttl: u32) -> serde_json::Value {
+    serde_json::json!({
+        "batchID": BATCH_ID,
+        "utilization": utilization,
+        "usable": usable,
+        "label": "service",
+        "depth": 20,
+        "amount": "100000000",
+        "bucketDepth": 16,
+        "blockNumber": 12345,
+        "immutableFlag": true,
+        "exists": true,
+        "batchTTL": ttl,
+    })
+}
+
+/// Serves a batch that changes only once it is diluted or topped up.
+#[derive(Clone)]
+struct BatchNode {
+    batch: Arc<Mutex<serde_json::Value>>,
+}
+
+impl BatchNode {
+    fn new(batch: serde_json::Value) -> Self {
+        Self { batch: Arc::new(Mutex::new(batch)) }
+    }
+}
+
+impl Respond for BatchNode {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut batch = self.batch.lock().unwrap();
+        if request.method.as_str() == "GET" {
+            return ResponseTemplate::new(200).set_body_json(batch.clone());
+        }
+
+        let segments: Vec<&str> = request.url.path().split('/').collect();
+        let value: u64 = segments[4].parse().unwrap();
+        let ttl = batch["batchTTL"].as_u64().unwrap();
+        if segments[2] == "dilute" {
+            // Diluting spreads the remaining balance over twice the chunks.
+            batch["depth"] = value.into();
+            batch["batchTTL"] = (ttl / 2).into();
+        } else {
+            let amount: u64 = batch["amount"].as_str().unwrap().parse().unwrap();
+            batch["amount"] = (amount + value).to_string().into();
+            batch["batchTTL"] = (ttl + value / 24_000 * 5).into();
+        }
+        ResponseTemplate::new(202).set_body_json(serde_json::json!({ "batchID": BATCH_ID, "txHash": TX_HASH }))
+    }
+}
+
+fn policy() -> BatchPolicy {
+    BatchPolicy {
+        usable_timeout: Duration::from_millis(200),
+        poll_interval: Duration::from_millis(10),
+        ..BatchPolicy::default()
+    }
+}
+
+async fn mock_chain_state(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/chainstate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "block": 1,
+            "chainTip": 1,
+            "totalAmount": "1",
+            "currentPrice": "24000",
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_maintain_healthy_batch() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch(4, true, 30 * 24 * 60 * 60)))
+        .mount(&server)
+        .await;
+
+    let manager = BatchManager::new(&server.uri(), policy()).unwrap();
+    let report = manager.maintain(BATCH_ID).await.unwrap();
+    assert!(report.actions.is_empty());
+    assert_eq!(report.batch.utilization, 4);
+}
+
+#[tokio::test]
+async fn test_maintain_dilutes_and_tops_up() {
+    let server = MockServer::start().await;
+    mock_chain_state(&server).await;
+    let node = BatchNode::new(batch(14, true, 3600));
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(node.clone())
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/stamps/dilute/{}/21", BATCH_ID)))
+        .respond_with(node.clone())
+        .expect(1)
+        .mount(&server)
+        .await;
+    // Diluting halves the time to live to 30 minutes, and
+    // (7 days - 30 minutes) / 5 seconds = 120600 blocks at 24000 PLUR each.
+    Mock::given(method("PATCH"))
+        .and(path(format!("/stamps/topup/{}/2894400000", BATCH_ID)))
+        .respond_with(node.clone())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let manager = BatchManager::new(&server.uri(), policy()).unwrap();
+    let report = manager.maintain(BATCH_ID).await.unwrap();
+    assert_eq!(
+        report.actions,
+        vec![
+            BatchAction::Diluted { depth: 21 },
+            BatchAction::ToppedUp { amount: BZZ::from_plur(2_894_400_000u64) },
+        ]
+    );
+    assert_eq!(report.batch.depth, 21);
+    assert_eq!(report.batch.ttl(), Some(Duration::from_secs(7 * 24 * 60 * 60)));
+}
+
+#[tokio::test]
+async fn test_maintain_caps_dilution_at_max_depth() {
+    let server = MockServer::start().await;
+    let mut deep_batch = batch(14, true, 30 * 24 * 60 * 60);
+    deep_batch["depth"] = 200.into();
+    deep_batch["bucketDepth"] = 196.into();
+    let node = BatchNode::new(deep_batch);
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(node.clone())
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/stamps/dilute/{}/255", BATCH_ID)))
+        .respond_with(node.clone())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let policy = BatchPolicy {
+        dilute_by: 100,
+        max_depth: 255,
+        ..policy()
+    };
+    let manager = BatchManager::new(&server.uri(), policy).unwrap();
+    let report = manager.maintain(BATCH_ID).await.unwrap();
+    assert_eq!(report.actions, vec![BatchAction::Diluted { depth: 255 }]);
+}
+
+#[test]
+fn test_policy_must_dilute() {
+    let policy = BatchPolicy {
+        dilute_by: 0,
+        ..policy()
+    };
+    let result = BatchManager::new("http://localhost:1635", policy);
+    assert!(matches!(result, Err(BatchManagerError::InvalidPolicy(_))));
+}
+
+#[tokio::test]
+async fn test_maintain_waits_for_dilute() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch(14, true, 30 * 24 * 60 * 60)))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/stamps/dilute/{}/21", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({ "batchID": BATCH_ID, "txHash": TX_HASH })))
+        .mount(&server)
+        .await;
+
+    // The node keeps reporting the old depth.
+    let manager = BatchManager::new(&server.uri(), policy()).unwrap();
+    let result = manager.maintain(BATCH_ID).await;
+    assert!(matches!(
+        result,
+        Err(BatchManagerError::Stamps(BeeDebugStampsError::NotUsable(id, _))) if id == BATCH_ID
+    ));
+}
+
+#[tokio::test]
+async fn test_create_waits_until_usable() {
+    let server = MockServer::start().await;
+    mock_chain_state(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/stamps/2903040000/22"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "batchID": BATCH_ID, "txHash": TX_HASH })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(404))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch(0, false, 604800)))
+        .up_to_n_times(1)
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch(0, true, 604800)))
+        .with_priority(3)
+        .mount(&server)
+        .await;
+
+    let manager = BatchManager::new(&server.uri(), policy()).unwrap();
+    let batch = manager.create(22, Some("service")).await.unwrap();
+    assert!(batch.usable);
+}
+
+#[tokio::test]
+async fn test_create_for_size() {
+    let server = MockServer::start().await;
+    mock_chain_state(&server).await;
+    // 5 GB need depth 23, 3 weeks are 362880 blocks at 24000 PLUR each.
+    Mock::given(method("POST"))
+        .and(path("/stamps/8709120000/23"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "batchID": BATCH_ID, "txHash": TX_HASH })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch(0, true, 1814400)))
+        .mount(&server)
+        .await;
+
+    let manager = BatchManager::new(&server.uri(), policy()).unwrap();
+    let ttl = parse_duration("3 weeks").unwrap();
+    let batch = manager.create_for_size("5GB".parse().unwrap(), ttl, None).await.unwrap();
+    assert_eq!(batch.ttl(), Some(ttl));
+}
+
+#[tokio::test]
+async fn test_wait_until_usable_times_out() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch(16, false, 3600)))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/stamps/dilute/{}/21", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({ "batchID": BATCH_ID, "txHash": TX_HASH })))
+        .mount(&server)
+        .await;
+
+    let manager = BatchManager::new(&server.uri(), policy()).unwrap();
+    let result = manager.maintain(BATCH_ID).await;
+    assert!(matches!(
+        result,
+        Err(BatchManagerError::Stamps(BeeDebugStampsError::NotUsable(id, _))) if id == BATCH_ID
+    ));
+}
+
+#[tokio::test]
+async fn test_monitor_survives_transient_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch(4, true, 30 * 24 * 60 * 60)))
+        .up_to_n_times(1)
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    // The batch expires.
+    Mock::given(method("GET"))
+        .and(path(format!("/stamps/{}", BATCH_ID)))
+        .respond_with(ResponseTemplate::new(404))
+        .with_priority(3)
+        .mount(&server)
+        .await;
+
+    let manager = BatchManager::new(&server.uri(), policy()).unwrap();
+    let errors = Cell::new(0);
+    let result = manager
+        .monitor(
+            BATCH_ID,
+            Duration::from_millis(10),
+            |_| panic!("the batch needs no changes"),
+            |e| {
+                assert!(e.is_transient());
+                errors.set(errors.get() + 1);
+            },
+        )
+        .await;
+    assert_eq!(errors.get(), 2);
+    assert!(matches!(result, Err(ref e) if !e.is_transient()));
+    assert_eq!(server.received_requests().await.unwrap().len(), 4);
+}
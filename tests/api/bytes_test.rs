@@ -1,7 +1,11 @@
-use bee_rs::api::bytes::{download, head, upload, DownloadOptions, RedundantUploadOptions, UploadOptions, UploadResult, ReferenceInformation};
-use wiremock::{matchers::{method, path_regex, header}, Mock, MockServer, ResponseTemplate};
+use bee_rs::api::bytes::{download, download_range, download_readable, download_to_file, exists, head, ByteRange, ContentRange, upload, upload_from_file, upload_from_reader, DownloadOptions, RedundantUploadOptions, UploadOptions, UploadResult, ReferenceInformation};
+use wiremock::{matchers::{method, path_regex, header}, Mock, MockServer, Request, Respond, ResponseTemplate};
 use serde_json;
 use reqwest::header::HeaderValue;
+use futures::TryStreamExt;
+use bee_rs::error::Error;
+
+mod common;
 
 #[tokio::test]
 async fn test_upload() {
@@ -54,7 +58,9 @@ async fn test_head() {
     let mock_server = MockServer::start().await;
     Mock::given(method("HEAD"))
         .and(path_regex("/bytes/(.*)"))
-        .respond_with(ResponseTemplate::new(200).insert_header("content-length", expected_content_length.to_string()))
+        .respond_with(ResponseTemplate::new(200)
+            .insert_header("content-length", expected_content_length.to_string())
+            .insert_header("content-type", "application/octet-stream"))
         .mount(&mock_server)
         .await;
 
@@ -66,7 +72,50 @@ async fn test_head() {
 
     assert!(result.is_ok());
     let info = result.unwrap();
-    assert_eq!(info.content_length, expected_content_length);
+    assert_eq!(info.content_length, Some(expected_content_length));
+    assert_eq!(info.content_type.as_deref(), Some("application/octet-stream"));
+}
+
+#[tokio::test]
+async fn test_head_not_found() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/bytes/(.*)"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let result = head(&client, &mock_server.uri(), "missing_reference").await;
+
+    assert!(matches!(result, Err(Error::NotFound(reference)) if reference == "missing_reference"));
+}
+
+#[tokio::test]
+async fn test_exists() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/bytes/stored_reference"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-length", "5"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/bytes/missing_reference"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/bytes/broken_reference"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    assert!(exists(&client, base_url, "stored_reference").await.unwrap());
+    assert!(!exists(&client, base_url, "missing_reference").await.unwrap());
+    assert!(exists(&client, base_url, "broken_reference").await.is_err());
 }
 
 #[tokio::test]
@@ -98,3 +147,279 @@ async fn test_download() {
     let downloaded_data = result.unwrap();
     assert_eq!(downloaded_data, expected_data);
 }
+
+#[tokio::test]
+async fn test_upload_from_reader() {
+    let expected_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+    let data: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex("/bytes"))
+        .and(header("content-type", "application/octet-stream"))
+        .and(header("content-length", data.len().to_string().as_str()))
+        .and(header("swarm-postage-batch-id", "test_batch_id"))
+        .and(header("swarm-pin", "true"))
+        .respond_with(ResponseTemplate::new(201)
+            .set_body_json(serde_json::json!({ "reference": expected_reference }))
+            .insert_header("swarm-tag", "9"))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+    let options = Some(RedundantUploadOptions {
+        upload_options: UploadOptions {
+            pin: Some(true),
+            ..Default::default()
+        },
+        redundancy_level: None,
+    });
+
+    let reader = std::io::Cursor::new(data.clone());
+    let result = upload_from_reader(&client, base_url, reader, Some(data.len() as u64), "test_batch_id", options.clone())
+        .await
+        .unwrap();
+    assert_eq!(result.reference, expected_reference);
+    assert_eq!(result.tag_uid, Some(9));
+
+    let path = common::temp_path("bytes-upload");
+    std::fs::write(&path, &data).unwrap();
+    let file = tokio::fs::File::open(&path).await.unwrap();
+    let result = upload_from_file(&client, base_url, file, "test_batch_id", options).await;
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result.unwrap().reference, expected_reference);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, data);
+    assert_eq!(requests[1].body, data);
+}
+
+#[tokio::test]
+async fn test_upload_from_reader_shorter_than_size() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex("/bytes"))
+        .respond_with(ResponseTemplate::new(201)
+            .set_body_json(serde_json::json!({ "reference": "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f" })))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let reader = std::io::Cursor::new(vec![1u8; 10]);
+    let result = upload_from_reader(&client, &mock_server.uri(), reader, Some(20), "test_batch_id", None).await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_download_readable() {
+    let expected_data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/bytes/(.*)"))
+        .and(header("swarm-redundancy-strategy", "1"))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_raw(expected_data.clone(), "application/octet-stream"))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+    let options = DownloadOptions {
+        redundancy_strategy: Some(1),
+        ..Default::default()
+    };
+
+    let download = download_readable(&client, base_url, "test_reference", Some(options.clone())).await.unwrap();
+    assert_eq!(download.content_type.as_deref(), Some("application/octet-stream"));
+    assert_eq!(download.content_length, Some(expected_data.len() as u64));
+    let chunks: Vec<bytes::Bytes> = download.stream.try_collect().await.unwrap();
+    assert_eq!(chunks.concat(), expected_data);
+
+    let download = download_readable(&client, base_url, "test_reference", Some(options)).await.unwrap();
+    let mut data = Vec::new();
+    tokio::io::copy(&mut download.into_async_read(), &mut data).await.unwrap();
+    assert_eq!(data, expected_data);
+}
+
+#[tokio::test]
+async fn test_download_readable_not_found() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/bytes/(.*)"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let result = download_readable(&client, &mock_server.uri(), "test_reference", None).await;
+
+    assert!(result.is_err());
+}
+
+/// Serves `data` for HEAD and GET requests, honouring `Range` headers of the
+/// form `bytes=<start>-` unless `ignore_range` is set.
+#[derive(Clone)]
+struct RangeServer {
+    data: Vec<u8>,
+    ignore_range: bool,
+}
+
+impl Respond for RangeServer {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        if request.method.as_str() == "HEAD" {
+            return ResponseTemplate::new(200).insert_header("content-length", self.data.len().to_string());
+        }
+        let range = request
+            .headers
+            .get("range")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("bytes="))
+            .and_then(|value| value.split_once('-'));
+        match range {
+            Some((start, end)) if !self.ignore_range => {
+                let start: usize = start.parse().unwrap();
+                let end: usize = if end.is_empty() { self.data.len() - 1 } else { end.parse().unwrap() };
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", format!("bytes {}-{}/{}", start, end, self.data.len()))
+                    .set_body_bytes(self.data[start..=end].to_vec())
+            }
+            _ => ResponseTemplate::new(200).set_body_bytes(self.data.clone()),
+        }
+    }
+}
+
+async fn start_range_server(data: &[u8], ignore_range: bool) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(path_regex("/bytes/(.*)"))
+        .respond_with(RangeServer { data: data.to_vec(), ignore_range })
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+#[test]
+fn test_byte_range() {
+    assert_eq!(ByteRange::new(0, 99).to_string(), "bytes=0-99");
+    assert_eq!(ByteRange::from_offset(100).to_string(), "bytes=100-");
+    assert_eq!(
+        ContentRange::parse("bytes 100-199/1000"),
+        Some(ContentRange { start: 100, end: 199, size: Some(1000) })
+    );
+    assert_eq!(ContentRange::parse("bytes 0-9/*").unwrap().size, None);
+    assert_eq!(ContentRange::parse("items 0-9/10"), None);
+}
+
+#[tokio::test]
+async fn test_download_range() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mock_server = start_range_server(&data, false).await;
+
+    let client = reqwest::Client::new();
+    let download = download_range(&client, &mock_server.uri(), "test_reference", ByteRange::new(10, 19), None)
+        .await
+        .unwrap();
+
+    assert_eq!(download.content_range, Some(ContentRange { start: 10, end: 19, size: Some(256) }));
+    let chunks: Vec<bytes::Bytes> = download.stream.try_collect().await.unwrap();
+    assert_eq!(chunks.concat(), data[10..20].to_vec());
+}
+
+#[tokio::test]
+async fn test_download_to_file_resumes() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    let mock_server = start_range_server(&data, false).await;
+
+    let client = reqwest::Client::new();
+    let path = common::temp_path("download-resume");
+    std::fs::write(&path, &data[..4000]).unwrap();
+
+    let size = download_to_file(&client, &mock_server.uri(), "test_reference", &path, None).await.unwrap();
+    let written = std::fs::read(&path).unwrap();
+
+    // Only the missing part was requested.
+    let requests = mock_server.received_requests().await.unwrap();
+    let get = requests.iter().find(|request| request.method.as_str() == "GET").unwrap();
+    assert_eq!(get.headers.get("range").unwrap(), "bytes=4000-");
+
+    // A complete file is not downloaded again.
+    download_to_file(&client, &mock_server.uri(), "test_reference", &path, None).await.unwrap();
+    let requests = mock_server.received_requests().await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(size, data.len() as u64);
+    assert_eq!(written, data);
+    assert_eq!(requests.iter().filter(|request| request.method.as_str() == "GET").count(), 1);
+}
+
+#[tokio::test]
+async fn test_download_to_file_without_range_support() {
+    let data: Vec<u8> = (0..5000u32).map(|i| (i * 7) as u8).collect();
+    let mock_server = start_range_server(&data, true).await;
+
+    let client = reqwest::Client::new();
+    let path = common::temp_path("download-no-range");
+    std::fs::write(&path, &data[..1000]).unwrap();
+
+    let size = download_to_file(&client, &mock_server.uri(), "test_reference", &path, None).await.unwrap();
+    let written = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(size, data.len() as u64);
+    assert_eq!(written, data);
+}
+
+#[tokio::test]
+async fn test_download_to_file_with_wrong_range() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i * 3) as u8).collect();
+    let mock_server = start_range_server(&data, false).await;
+    Mock::given(method("GET"))
+        .and(header("range", "bytes=4000-"))
+        .respond_with(ResponseTemplate::new(206)
+            .insert_header("content-range", "bytes 2000-9999/10000")
+            .set_body_bytes(data[2000..].to_vec()))
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let path = common::temp_path("download-wrong-range");
+    std::fs::write(&path, &data[..4000]).unwrap();
+
+    let size = download_to_file(&client, &mock_server.uri(), "test_reference", &path, None).await.unwrap();
+    let written = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // The mismatched range is dropped and the whole content downloaded again.
+    let requests = mock_server.received_requests().await.unwrap();
+    let gets: Vec<_> = requests.iter().filter(|request| request.method.as_str() == "GET").collect();
+    assert_eq!(gets.len(), 2);
+    assert!(gets[1].headers.get("range").is_none());
+    assert_eq!(size, data.len() as u64);
+    assert_eq!(written, data);
+}
+
+#[tokio::test]
+async fn test_download_to_file_size_mismatch() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/bytes/(.*)"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-length", "10"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex("/bytes/(.*)"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![1, 2, 3]))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let path = common::temp_path("download-mismatch");
+    let result = download_to_file(&client, &mock_server.uri(), "test_reference", &path, None).await;
+    let _ = std::fs::remove_file(&path);
+
+    assert!(result.is_err());
+}
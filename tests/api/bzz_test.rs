@@ -1,8 +1,16 @@
-use bee_rs::api::bzz::{download_file, upload_file, FileData, FileUploadOptions, CollectionUploadOptions};
-use bee_rs::api::bytes::{DownloadOptions, RedundantUploadOptions, UploadOptions, UploadResult};
+use bee_rs::api::bzz::{self, download_collection, download_file, download_file_range, download_file_readable, list_collection, sync_directory, upload_collection, upload_file, upload_file_from_file, upload_file_from_reader, upload_files_from_directory, CollectionDownloadOptions, CollectionEntry, CollectionUploadOptions, DirectoryUploadOptions, FileData, FileUploadOptions, SyncOptions};
+use bee_rs::api::bytes::{self, ByteRange, ContentRange, DownloadOptions, RedundantUploadOptions, UploadOptions, UploadResult};
+use bee_rs::api::manifest::{Manifest, CONTENT_TYPE_KEY, FILENAME_KEY};
+use bee_rs::error::Error;
+use bee_rs::utils::merkle_tree::file_address;
+use std::collections::BTreeMap;
+use std::io::Read;
+use futures::TryStreamExt;
 use wiremock::{matchers::{method, path_regex, header, query_param}, Mock, MockServer, ResponseTemplate};
 use serde_json;
 
+mod common;
+
 #[tokio::test]
 async fn test_upload_file() {
     let expected_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
@@ -50,6 +58,54 @@ async fn test_upload_file() {
     assert_eq!(upload_result.history_address, Some(expected_history_address.to_string()));
 }
 
+#[tokio::test]
+async fn test_upload_file_streaming() {
+    let expected_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+    let data = b"streamed file content".to_vec();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex("/bzz"))
+        .and(query_param("name", "notes.txt"))
+        .and(header("content-type", "text/plain"))
+        .and(header("content-length", data.len().to_string().as_str()))
+        .and(header("swarm-postage-batch-id", "test_batch_id"))
+        .respond_with(ResponseTemplate::new(201)
+            .set_body_json(serde_json::json!({ "reference": expected_reference }))
+            .insert_header("swarm-tag", "3"))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+    let options = || {
+        Some(FileUploadOptions {
+            content_type: Some("text/plain".to_string()),
+            ..Default::default()
+        })
+    };
+
+    let path = common::temp_path("bzz-upload");
+    std::fs::write(&path, &data).unwrap();
+    let file = tokio::fs::File::open(&path).await.unwrap();
+    let result = upload_file_from_file(&client, base_url, file, "test_batch_id", Some("notes.txt"), options()).await;
+    std::fs::remove_file(&path).unwrap();
+    let result = result.unwrap();
+    assert_eq!(result.reference, expected_reference);
+    assert_eq!(result.tag_uid, Some(3));
+
+    let mut reader_options = options();
+    reader_options.as_mut().unwrap().size = Some(data.len() as u64);
+    let reader = std::io::Cursor::new(data.clone());
+    let result = upload_file_from_reader(&client, base_url, reader, "test_batch_id", Some("notes.txt"), reader_options).await;
+    assert_eq!(result.unwrap().reference, expected_reference);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, data);
+    assert_eq!(requests[1].body, data);
+}
+
 #[tokio::test]
 async fn test_download_file() {
     let expected_data = vec![1, 2, 3, 4, 5];
@@ -90,3 +146,404 @@ async fn test_download_file() {
     assert_eq!(file_data.tag_uid, Some(expected_tag_uid));
     assert_eq!(file_data.content_type, Some(expected_content_type.to_string()));
 }
+
+#[tokio::test]
+async fn test_download_file_readable() {
+    let expected_data = b"file content".to_vec();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/bzz/test_reference/docs/readme.txt"))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_raw(expected_data.clone(), "text/plain")
+            .insert_header("swarm-file-name", "readme.txt"))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let download = download_file_readable(&client, &mock_server.uri(), "test_reference", Some("docs/readme.txt"), None)
+        .await
+        .unwrap();
+
+    assert_eq!(download.content_type.as_deref(), Some("text/plain"));
+    assert_eq!(download.file_name.as_deref(), Some("readme.txt"));
+    assert_eq!(download.content_length, Some(expected_data.len() as u64));
+    let chunks: Vec<::bytes::Bytes> = download.stream.try_collect().await.unwrap();
+    assert_eq!(chunks.concat(), expected_data);
+}
+
+#[tokio::test]
+async fn test_download_file_range() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/bzz/test_reference/video.mp4"))
+        .and(header("range", "bytes=2-5"))
+        .respond_with(ResponseTemplate::new(206)
+            .set_body_raw(b"2345".to_vec(), "video/mp4")
+            .insert_header("content-range", "bytes 2-5/10"))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let download = download_file_range(
+        &client,
+        &mock_server.uri(),
+        "test_reference",
+        Some("video.mp4"),
+        ByteRange::new(2, 5),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(download.content_type.as_deref(), Some("video/mp4"));
+    assert_eq!(download.content_range, Some(ContentRange { start: 2, end: 5, size: Some(10) }));
+    let chunks: Vec<::bytes::Bytes> = download.stream.try_collect().await.unwrap();
+    assert_eq!(chunks.concat(), b"2345".to_vec());
+}
+
+#[tokio::test]
+async fn test_head() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/bzz/test_reference/index.html"))
+        .respond_with(ResponseTemplate::new(200)
+            .insert_header("content-length", "13")
+            .insert_header("content-type", "text/html"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/bzz/test_reference/missing.html"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    let info = bzz::head(&client, base_url, "test_reference", Some("index.html")).await.unwrap();
+    assert_eq!(info.content_length, Some(13));
+    assert_eq!(info.content_type.as_deref(), Some("text/html"));
+
+    let result = bzz::head(&client, base_url, "test_reference", Some("missing.html")).await;
+    assert!(matches!(result, Err(Error::NotFound(resource)) if resource == "test_reference/missing.html"));
+
+    assert!(bzz::exists(&client, base_url, "test_reference", Some("index.html")).await.unwrap());
+    assert!(!bzz::exists(&client, base_url, "test_reference", Some("missing.html")).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_list_collection() {
+    let (mock_server, _store) = common::start_store().await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+    let index = bytes::upload(&client, base_url, b"<html></html>".to_vec(), "test_batch_id", None).await.unwrap();
+    let style = bytes::upload(&client, base_url, b"body {}".to_vec(), "test_batch_id", None).await.unwrap();
+
+    let mut manifest = Manifest::new(&client, base_url);
+    let metadata = BTreeMap::from([
+        (CONTENT_TYPE_KEY.to_string(), "text/html".to_string()),
+        (FILENAME_KEY.to_string(), "index.html".to_string()),
+    ]);
+    manifest.add_file("index.html", &index.reference, metadata).await.unwrap();
+    manifest.add_file("css/style.css", &style.reference, BTreeMap::new()).await.unwrap();
+    manifest.set_index_document("index.html").await.unwrap();
+    let reference = manifest.save("test_batch_id", None).await.unwrap();
+
+    let files = list_collection(&client, base_url, &reference).await.unwrap();
+
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].path, "css/style.css");
+    assert_eq!(files[0].reference, style.reference);
+    assert_eq!(files[0].content_type, None);
+    assert_eq!(files[0].size, 7);
+    assert_eq!(files[1].path, "index.html");
+    assert_eq!(files[1].content_type, Some("text/html".to_string()));
+    assert_eq!(files[1].file_name, Some("index.html".to_string()));
+    assert_eq!(files[1].size, 13);
+}
+
+#[tokio::test]
+async fn test_upload_collection() {
+    let expected_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex("/bzz"))
+        .and(header("content-type", "application/x-tar"))
+        .and(header("swarm-collection", "true"))
+        .and(header("swarm-postage-batch-id", "test_batch_id"))
+        .and(header("swarm-index-document", "index.html"))
+        .and(header("swarm-error-document", "404.html"))
+        .and(header("swarm-pin", "true"))
+        .and(header("swarm-redundancy-level", "1"))
+        .respond_with(ResponseTemplate::new(201)
+            .set_body_json(serde_json::json!({ "reference": expected_reference }))
+            .insert_header("swarm-tag", "42"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let entries = vec![
+        CollectionEntry::from_data("index.html", b"<html></html>".to_vec()),
+        CollectionEntry::from_data("404.html", b"not found".to_vec()),
+    ];
+    let options = Some(CollectionUploadOptions {
+        redundant_upload_options: RedundantUploadOptions {
+            upload_options: UploadOptions {
+                pin: Some(true),
+                ..Default::default()
+            },
+            redundancy_level: Some(1),
+        },
+        index_document: Some("index.html".to_string()),
+        error_document: Some("404.html".to_string()),
+    });
+
+    let result = upload_collection(&client, &mock_server.uri(), entries, "test_batch_id", options).await.unwrap();
+
+    assert_eq!(result.reference, expected_reference);
+    assert_eq!(result.tag_uid, Some(42));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let mut archive = tar::Archive::new(requests[0].body.as_slice());
+    let paths: Vec<String> = archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let mut content = String::new();
+            entry.read_to_string(&mut content).unwrap();
+            format!("{}={}", entry.path().unwrap().display(), content)
+        })
+        .collect();
+    assert_eq!(paths, vec!["index.html=<html></html>", "404.html=not found"]);
+}
+
+#[tokio::test]
+async fn test_upload_files_from_directory() {
+    let (mock_server, store) = common::start_store().await;
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    let dir = common::temp_dir("upload-dir");
+    std::fs::create_dir_all(dir.join("img")).unwrap();
+    std::fs::write(dir.join("index.html"), "<html></html>").unwrap();
+    std::fs::write(dir.join("notes.txt"), "hello").unwrap();
+    std::fs::write(dir.join("img/logo.svg"), "<svg/>").unwrap();
+    std::fs::write(dir.join("LICENSE"), "MIT").unwrap();
+    std::fs::write(dir.join("debug.log"), "ignored").unwrap();
+    let options = Some(DirectoryUploadOptions {
+        ignore: vec!["*.log".to_string()],
+        ..Default::default()
+    });
+
+    let result = upload_files_from_directory(&client, base_url, &dir, "test_batch_id", options).await.unwrap();
+    let uploads = store.uploads();
+
+    // The manifest is built locally with the detected content types.
+    let mut manifest = Manifest::load(&client, base_url, &result.reference).await.unwrap();
+    assert_eq!(manifest.index_document(), Some("index.html"));
+    let entries = manifest.entries().await.unwrap();
+    let metadata: Vec<(&str, Option<&str>, &str)> = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.path.as_str(),
+                entry.metadata.get(CONTENT_TYPE_KEY).map(String::as_str),
+                entry.metadata[FILENAME_KEY].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        metadata,
+        vec![
+            ("LICENSE", None, "LICENSE"),
+            ("img/logo.svg", Some("image/svg+xml"), "logo.svg"),
+            ("index.html", Some("text/html"), "index.html"),
+            ("notes.txt", Some("text/plain"), "notes.txt"),
+        ]
+    );
+    let notes = bytes::download(&client, base_url, &entries[3].reference, None).await.unwrap();
+    assert_eq!(&notes[..], b"hello");
+
+    // The uploaded collection is what sync_directory expects.
+    let options = Some(SyncOptions {
+        ignore: vec!["*.log".to_string()],
+        ..Default::default()
+    });
+    let report = sync_directory(&client, base_url, &dir, &result.reference, "test_batch_id", options).await.unwrap();
+    assert_eq!(report.unchanged.len(), 4);
+    assert_eq!(report.reference, result.reference);
+    assert_eq!(store.uploads(), uploads);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_upload_files_from_directory_rejects_act() {
+    let mock_server = MockServer::start().await;
+    let client = reqwest::Client::new();
+    let dir = common::temp_dir("upload-act");
+    let options = Some(DirectoryUploadOptions {
+        collection_upload_options: CollectionUploadOptions {
+            redundant_upload_options: RedundantUploadOptions {
+                upload_options: UploadOptions {
+                    act: Some(true),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let result = upload_files_from_directory(&client, &mock_server.uri(), &dir, "test_batch_id", options).await;
+    assert!(result.is_err());
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_download_collection() {
+    let (mock_server, store) = common::start_store().await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    // Files are stored under their Swarm reference so existing files can be recognized.
+    let mut manifest = Manifest::new(&client, base_url);
+    for (path, data) in [("index.html", &b"<html></html>"[..]), ("css/style.css", &b"body {}"[..])] {
+        let reference = hex::encode(file_address(data));
+        store.chunks.lock().unwrap().insert(reference.clone(), data.to_vec());
+        manifest.add_file(path, &reference, BTreeMap::new()).await.unwrap();
+    }
+    let reference = manifest.save("test_batch_id", None).await.unwrap();
+
+    let dir = common::temp_path("download-dir");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let report = download_collection(&client, base_url, &reference, &dir, None).await.unwrap();
+    assert_eq!(report.downloaded, vec!["css/style.css", "index.html"]);
+    assert!(report.skipped.is_empty());
+    assert_eq!(std::fs::read(dir.join("css/style.css")).unwrap(), b"body {}");
+    assert_eq!(std::fs::read(dir.join("index.html")).unwrap(), b"<html></html>");
+
+    std::fs::write(dir.join("index.html"), b"<html>changed</html>").unwrap();
+    let options = Some(CollectionDownloadOptions {
+        skip_existing: true,
+        concurrency: Some(1),
+        ..Default::default()
+    });
+    let report = download_collection(&client, base_url, &reference, &dir, options).await.unwrap();
+    assert_eq!(report.downloaded, vec!["index.html"]);
+    assert_eq!(report.skipped, vec!["css/style.css"]);
+    assert_eq!(std::fs::read(dir.join("index.html")).unwrap(), b"<html></html>");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_download_collection_server_error() {
+    let (mock_server, _store) = common::start_store().await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    let mut manifest = Manifest::new(&client, base_url);
+    let file = bytes::upload(&client, base_url, b"hello".to_vec(), "test_batch_id", None).await.unwrap();
+    manifest.add_file("hello.txt", &file.reference, BTreeMap::new()).await.unwrap();
+    let reference = manifest.save("test_batch_id", None).await.unwrap();
+
+    // The error body must not end up in the file.
+    Mock::given(method("GET"))
+        .and(path_regex(format!("/bytes/{}", file.reference)))
+        .respond_with(ResponseTemplate::new(500).set_body_string("fail"))
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+
+    let dir = common::temp_dir("download-error");
+    let result = download_collection(&client, base_url, &reference, &dir, None).await;
+    assert!(matches!(result, Err(Error::Reqwest(e)) if e.status() == Some(reqwest::StatusCode::INTERNAL_SERVER_ERROR)));
+    assert!(!dir.join("hello.txt").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_sync_directory() {
+    let (mock_server, _store) = common::start_store().await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    let mut manifest = Manifest::new(&client, base_url);
+    for (path, data) in [("index.html", &b"<html></html>"[..]), ("app.js", &b"old"[..]), ("old.txt", &b"gone"[..])] {
+        let file = bytes::upload(&client, base_url, data.to_vec(), "test_batch_id", None).await.unwrap();
+        manifest.add_file(path, &file.reference, BTreeMap::new()).await.unwrap();
+    }
+    manifest.set_index_document("index.html").await.unwrap();
+    let reference = manifest.save("test_batch_id", None).await.unwrap();
+
+    let dir = common::temp_path("sync-dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("img")).unwrap();
+    std::fs::write(dir.join("index.html"), b"<html></html>").unwrap();
+    std::fs::write(dir.join("app.js"), b"new").unwrap();
+    std::fs::write(dir.join("img/logo.svg"), b"<svg/>").unwrap();
+
+    let report = sync_directory(&client, base_url, &dir, &reference, "test_batch_id", None).await.unwrap();
+    assert_eq!(report.added, vec!["img/logo.svg"]);
+    assert_eq!(report.modified, vec!["app.js"]);
+    assert_eq!(report.removed, vec!["old.txt"]);
+    assert_eq!(report.unchanged, vec!["index.html"]);
+    assert_ne!(report.reference, reference);
+
+    let mut synced = Manifest::load(&client, base_url, &report.reference).await.unwrap();
+    assert_eq!(synced.index_document(), Some("index.html"));
+    let entries = synced.entries().await.unwrap();
+    let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+    assert_eq!(paths, vec!["app.js", "img/logo.svg", "index.html"]);
+    assert_eq!(entries[1].metadata[CONTENT_TYPE_KEY], "image/svg+xml");
+    assert_eq!(entries[1].metadata[FILENAME_KEY], "logo.svg");
+    let app = bytes::download(&client, base_url, &entries[0].reference, None).await.unwrap();
+    assert_eq!(&app[..], b"new");
+
+    // A second sync finds nothing to upload.
+    let report = sync_directory(&client, base_url, &dir, &report.reference, "test_batch_id", None).await.unwrap();
+    assert_eq!(report.unchanged.len(), 3);
+    assert!(report.added.is_empty() && report.modified.is_empty() && report.removed.is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_sync_directory_rejects_changed_references() {
+    let mock_server = MockServer::start().await;
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+    let dir = common::temp_dir("sync-rejected");
+
+    // Encrypted and erasure coded uploads never match the offline references.
+    for (encrypt, redundancy_level) in [(Some(true), None), (None, Some(1))] {
+        let options = Some(SyncOptions {
+            redundant_upload_options: RedundantUploadOptions {
+                upload_options: UploadOptions {
+                    encrypt,
+                    ..Default::default()
+                },
+                redundancy_level,
+            },
+            ..Default::default()
+        });
+        let result = sync_directory(&client, base_url, &dir, "test_reference", "test_batch_id", options).await;
+        assert!(matches!(result, Err(Error::Custom(_))));
+    }
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,60 @@
+use bee_rs::api::cheque::{domain_separator, parse_address, Cheque, SignedCheque, GNOSIS_CHAIN_ID};
+use bee_rs::api::debug::chequebook::Cheque as ChequeResponse;
+use bee_rs::api::signer::{PrivateKeySigner, Signer};
+use bee_rs::utils::tokens::BZZ;
+
+const PRIVATE_KEY: &str = "634fb5a872396d9693e5c9f9d7233cfa93f395c093371017ff44aa9ae6564cdd";
+const CHEQUEBOOK: &str = "0xfa02D396842E6e1D319E8E3D4D870338F791AA25";
+const BENEFICIARY: &str = "0x98E6C644aFeB94BBfB9FF60EB26fc9D83BBEcA79";
+
+fn cheque(cumulative_payout: i64) -> Cheque {
+    Cheque {
+        chequebook: parse_address(CHEQUEBOOK).unwrap(),
+        beneficiary: parse_address(BENEFICIARY).unwrap(),
+        cumulative_payout: BZZ::from_plur(cumulative_payout),
+    }
+}
+
+#[test]
+fn test_sign_cheque() {
+    // The cheque of Bee's chequebook signing test.
+    let signer = PrivateKeySigner::from_hex(PRIVATE_KEY).unwrap();
+    let signed = cheque(500).sign(&signer, 1).unwrap();
+    assert_eq!(
+        hex::encode(signed.signature),
+        "171b63fc598ae2c7987f4a756959dadddd84ccd2071e7b5c3aa3437357be47286125edc370c344a163ba7f4183dfd3611996274a13e4b3496610fc00c0e2fc421c"
+    );
+
+    assert_eq!(signed.recover_issuer(1).unwrap(), signer.address());
+    assert!(signed.verify(1, &signer.address()).is_ok());
+    // The signature is only valid for the chain and the issuer it was made for.
+    assert!(signed.verify(GNOSIS_CHAIN_ID, &signer.address()).is_err());
+    assert!(signed.verify(1, &parse_address(BENEFICIARY).unwrap()).is_err());
+
+    let tampered = SignedCheque { cheque: cheque(501), ..signed };
+    assert!(tampered.verify(1, &signer.address()).is_err());
+}
+
+#[test]
+fn test_cheque_from_response() {
+    let response = ChequeResponse {
+        beneficiary: BENEFICIARY.to_lowercase(),
+        chequebook: CHEQUEBOOK.trim_start_matches("0x").to_string(),
+        payout: BZZ::from_plur(500),
+    };
+    assert_eq!(Cheque::try_from(&response).unwrap(), cheque(500));
+
+    let response = ChequeResponse {
+        beneficiary: "0x1234".to_string(),
+        ..response
+    };
+    assert!(Cheque::try_from(&response).is_err());
+}
+
+#[test]
+fn test_hashes() {
+    assert_ne!(domain_separator(1), domain_separator(GNOSIS_CHAIN_ID));
+    assert_ne!(cheque(500).struct_hash().unwrap(), cheque(501).struct_hash().unwrap());
+    assert_ne!(cheque(500).signing_hash(1).unwrap(), cheque(500).signing_hash(GNOSIS_CHAIN_ID).unwrap());
+    assert!(cheque(-1).struct_hash().is_err());
+}
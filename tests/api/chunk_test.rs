@@ -1,4 +1,7 @@
-use bee_rs::api::chunk::{download, upload};
+use bee_rs::api::chunk::{download, download_readable, exists, head, upload, upload_with_stamp};
+use bee_rs::api::signer::PrivateKeySigner;
+use bee_rs::api::stamper::{Stamper, STAMP_SIZE};
+use bee_rs::error::Error;
 use bee_rs::api::bytes::{DownloadOptions, UploadOptions, UploadResult};
 use wiremock::{matchers::{method, path_regex, header}, Mock, MockServer, ResponseTemplate};
 use reqwest::header::HeaderValue;
@@ -74,3 +77,88 @@ async fn test_download_chunk() {
     let downloaded_data = result.unwrap();
     assert_eq!(downloaded_data, expected_data);
 }
+
+#[tokio::test]
+async fn test_download_chunk_readable() {
+    let expected_data = vec![10, 20, 30, 40, 50];
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/chunks/(.*)"))
+        .and(header("swarm-timeout", "1000"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(expected_data.clone()))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let options = Some(DownloadOptions {
+        timeout_ms: Some(1000),
+        ..Default::default()
+    });
+
+    let download = download_readable(&client, &mock_server.uri(), "test_chunk_reference", options).await.unwrap();
+    assert_eq!(download.content_length, Some(5));
+
+    let mut data = Vec::new();
+    tokio::io::copy(&mut download.into_async_read(), &mut data).await.unwrap();
+    assert_eq!(data, expected_data);
+}
+
+#[tokio::test]
+async fn test_head_chunk() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/chunks/stored_chunk"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path_regex("/chunks/missing_chunk"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    // Bee answers with an empty 200 for stored chunks.
+    let info = head(&client, base_url, "stored_chunk").await.unwrap();
+    assert_eq!(info.content_length, None);
+
+    let result = head(&client, base_url, "missing_chunk").await;
+    assert!(matches!(result, Err(Error::NotFound(_))));
+
+    assert!(exists(&client, base_url, "stored_chunk").await.unwrap());
+    assert!(!exists(&client, base_url, "missing_chunk").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_upload_chunk_with_stamp() {
+    let expected_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+    let signer = PrivateKeySigner::from_hex("634fb5a872396d9693e5c9f9d7233cfa93f395c093371017ff44aa9ae6564cdd").unwrap();
+    let batch_id = "b330000000000000000000000000000000000000000000000000000000000000";
+    let mut stamper = Stamper::new(signer, batch_id, 20).unwrap();
+    let stamp = stamper.stamp(&hex_literal::hex!("36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f")).unwrap();
+    let serialized = stamp.to_bytes();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex("/chunks"))
+        .and(header("swarm-postage-stamp", hex::encode(serialized).as_str()))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "reference": expected_reference })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let result = upload_with_stamp(&client, &mock_server.uri(), vec![1, 2, 3], &stamp, None).await.unwrap();
+    assert_eq!(result.reference, expected_reference);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(requests[0].headers.get("swarm-postage-batch-id").is_none());
+    assert_eq!(serialized.len(), STAMP_SIZE);
+    assert_eq!(hex::encode(&serialized[..32]), batch_id);
+    assert_eq!(&serialized[32..40], &stamp.index);
+    assert_eq!(serialized[40..48], stamp.timestamp.to_be_bytes());
+    assert_eq!(&serialized[48..], &stamp.signature);
+}
@@ -0,0 +1,67 @@
+use bee_rs::utils::collection::{collection_size, detect_index_document, IgnoreRules};
+use bee_rs::utils::collection_node::make_collection_from_fs;
+use bee_rs::utils::mime::content_type;
+use std::fs;
+
+mod common;
+
+#[test]
+fn test_content_type() {
+    assert_eq!(content_type("index.html"), Some("text/html"));
+    assert_eq!(content_type("assets/font.WOFF2"), Some("font/woff2"));
+    assert_eq!(content_type("archive.tar.gz"), Some("application/gzip"));
+    assert_eq!(content_type("README"), None);
+    assert_eq!(content_type(".gitignore"), None);
+    assert_eq!(content_type("file.unknown"), None);
+}
+
+#[test]
+fn test_ignore_rules() {
+    let rules = IgnoreRules::parse("# comment\n\n*.log\nnode_modules/\n/build\ndocs/**/draft-*.md\n!keep.log\n");
+
+    assert!(rules.is_ignored("debug.log", false));
+    assert!(rules.is_ignored("nested/dir/debug.log", false));
+    assert!(!rules.is_ignored("keep.log", false));
+    assert!(rules.is_ignored("node_modules", true));
+    assert!(rules.is_ignored("src/node_modules", true));
+    assert!(!rules.is_ignored("node_modules", false));
+    assert!(rules.is_ignored("build", true));
+    assert!(!rules.is_ignored("src/build", true));
+    assert!(rules.is_ignored("docs/draft-1.md", false));
+    assert!(rules.is_ignored("docs/a/b/draft-2.md", false));
+    assert!(!rules.is_ignored("docs/final.md", false));
+}
+
+#[test]
+fn test_make_collection_from_fs() {
+    let dir = common::temp_dir("collection");
+    fs::create_dir_all(dir.join("css")).unwrap();
+    fs::create_dir_all(dir.join("empty")).unwrap();
+    fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+    fs::write(dir.join("index.html"), "<html></html>").unwrap();
+    fs::write(dir.join("css/style.css"), "body {}").unwrap();
+    fs::write(dir.join("debug.log"), "log").unwrap();
+    fs::write(dir.join("node_modules/pkg/index.js"), "module").unwrap();
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(dir.join("index.html"), dir.join("link.html")).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("css/loop")).unwrap();
+        std::os::unix::fs::symlink(dir.join("missing"), dir.join("dangling")).unwrap();
+    }
+
+    let rules = IgnoreRules::new(["*.log", "node_modules/"]);
+    let entries = make_collection_from_fs(&dir, &rules, false).unwrap();
+    let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+    assert_eq!(paths, vec!["css/style.css", "index.html"]);
+    assert_eq!(collection_size(&entries), 20);
+    assert_eq!(detect_index_document(&entries), Some("index.html".to_string()));
+
+    #[cfg(unix)]
+    {
+        let entries = make_collection_from_fs(&dir, &rules, true).unwrap();
+        let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, vec!["css/style.css", "index.html", "link.html"]);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,64 @@
+//! Helpers shared by the API tests.
+
+#![allow(dead_code)]
+
+use bee_rs::utils::merkle_tree::file_address;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use wiremock::{matchers::path_regex, Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Stores uploaded bytes under their Swarm reference and serves them back.
+#[derive(Clone, Default)]
+pub struct BytesStore {
+    pub chunks: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    pub uploads: Arc<Mutex<usize>>,
+}
+
+impl BytesStore {
+    /// The number of uploads so far.
+    pub fn uploads(&self) -> usize {
+        *self.uploads.lock().unwrap()
+    }
+}
+
+impl Respond for BytesStore {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        if request.method.as_str() == "POST" {
+            let reference = hex::encode(file_address(&request.body));
+            self.chunks.lock().unwrap().insert(reference.clone(), request.body.clone());
+            *self.uploads.lock().unwrap() += 1;
+            return ResponseTemplate::new(201).set_body_json(serde_json::json!({ "reference": reference }));
+        }
+
+        let reference = request.url.path().trim_start_matches("/bytes/");
+        match self.chunks.lock().unwrap().get(reference) {
+            Some(data) => ResponseTemplate::new(200).set_body_bytes(data.clone()),
+            None => ResponseTemplate::new(404),
+        }
+    }
+}
+
+/// Start a mock node serving `/bytes` from a [`BytesStore`].
+pub async fn start_store() -> (MockServer, BytesStore) {
+    let mock_server = MockServer::start().await;
+    let store = BytesStore::default();
+    Mock::given(path_regex("/bytes.*"))
+        .respond_with(store.clone())
+        .mount(&mock_server)
+        .await;
+    (mock_server, store)
+}
+
+/// A path in the temporary directory that is unique to the test process.
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("bee-rs-{}-{}", name, std::process::id()))
+}
+
+/// An empty directory at [`temp_path`].
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = temp_path(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
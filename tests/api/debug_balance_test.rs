@@ -1,4 +1,5 @@
 use bee_rs::api::debug::balance::{BeeDebugBalanceClient, BalanceResponse, PeerBalance};
+use bee_rs::utils::tokens::BZZ;
 use wiremock::{matchers::{method, path_regex}, Mock, MockServer, ResponseTemplate};
 use serde_json;
 
@@ -29,7 +30,7 @@ async fn test_get_all_balances() {
     let balances = result.unwrap();
     assert_eq!(balances.balances.len(), 2);
     assert_eq!(balances.balances[0].peer, "0x1234567890123456789012345678901234567890");
-    assert_eq!(balances.balances[0].balance, "100000000000000000000");
+    assert_eq!(balances.balances[0].balance, BZZ::from_plur_str("100000000000000000000").unwrap());
 }
 
 #[tokio::test]
@@ -50,7 +51,7 @@ async fn test_get_peer_balance() {
     assert!(result.is_ok());
     let peer_balance = result.unwrap();
     assert_eq!(peer_balance.peer, "0x1234567890123456789012345678901234567890");
-    assert_eq!(peer_balance.balance, "100000000000000000000");
+    assert_eq!(peer_balance.balance, BZZ::from_plur_str("100000000000000000000").unwrap());
 }
 
 #[tokio::test]
@@ -76,7 +77,7 @@ async fn test_get_past_due_consumption_balances() {
     let balances = result.unwrap();
     assert_eq!(balances.balances.len(), 1);
     assert_eq!(balances.balances[0].peer, "0x1234567890123456789012345678901234567890");
-    assert_eq!(balances.balances[0].balance, "100000000000000000000");
+    assert_eq!(balances.balances[0].balance, BZZ::from_plur_str("100000000000000000000").unwrap());
 }
 
 #[tokio::test]
@@ -97,5 +98,5 @@ async fn test_get_past_due_consumption_peer_balance() {
     assert!(result.is_ok());
     let peer_balance = result.unwrap();
     assert_eq!(peer_balance.peer, "0x1234567890123456789012345678901234567890");
-    assert_eq!(peer_balance.balance, "100000000000000000000");
+    assert_eq!(peer_balance.balance, BZZ::from_plur_str("100000000000000000000").unwrap());
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use bee_rs::api::debug::chequebook::{BeeDebugChequebookClient, ChequebookAddressResponse, ChequebookBalanceResponse, LastCashoutActionResponse, LastChequesForPeerResponse, LastChequesResponse, Cheque, CashoutResult};
+use bee_rs::utils::tokens::BZZ;
 use wiremock::{matchers::{method, path_regex}, Mock, MockServer, ResponseTemplate};
 use serde_json;
 use std::collections::HashMap;
@@ -27,8 +28,8 @@ async fn test_get_chequebook_balance() {
     Mock::given(method("GET"))
         .and(path_regex("/chequebook/balance"))
         .respond_with(ResponseTemplate::new(200).set_body_json(ChequebookBalanceResponse {
-            total_balance: "100000000000000000000".to_string(),
-            available_balance: "50000000000000000000".to_string(),
+            total_balance: BZZ::from_plur_str("100000000000000000000").unwrap(),
+            available_balance: BZZ::from_plur_str("50000000000000000000").unwrap(),
         }))
         .mount(&mock_server)
         .await;
@@ -37,8 +38,8 @@ async fn test_get_chequebook_balance() {
     let result = client.get_chequebook_balance().await;
     assert!(result.is_ok());
     let response = result.unwrap();
-    assert_eq!(response.total_balance, "100000000000000000000");
-    assert_eq!(response.available_balance, "50000000000000000000");
+    assert_eq!(response.total_balance, BZZ::from_plur_str("100000000000000000000").unwrap());
+    assert_eq!(response.available_balance, BZZ::from_plur_str("50000000000000000000").unwrap());
 }
 
 #[tokio::test]
@@ -48,16 +49,16 @@ async fn test_get_last_cashout_action() {
         .and(path_regex("/chequebook/cashout/(.*)"))
         .respond_with(ResponseTemplate::new(200).set_body_json(LastCashoutActionResponse {
             peer: "0x1234567890123456789012345678901234567890123456789012345678901234".to_string(),
-            uncashed_amount: "10000000000000000000".to_string(),
+            uncashed_amount: BZZ::from_plur_str("10000000000000000000").unwrap(),
             transaction_hash: Some("0x1234567890123456789012345678901234567890123456789012345678901234".to_string()),
             last_cashed_cheque: Some(Cheque {
                 beneficiary: "0x1234567890123456789012345678901234567890".to_string(),
                 chequebook: "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd".to_string(),
-                payout: "1000000000000000000".to_string(),
+                payout: BZZ::from_plur_str("1000000000000000000").unwrap(),
             }),
             result: Some(CashoutResult {
                 recipient: "0x1234567890123456789012345678901234567890".to_string(),
-                last_payout: "1000000000000000000".to_string(),
+                last_payout: BZZ::from_plur_str("1000000000000000000").unwrap(),
                 bounced: false,
             }),
         }))
@@ -68,7 +69,7 @@ async fn test_get_last_cashout_action() {
     let result = client.get_last_cashout_action("0x1234567890123456789012345678901234567890123456789012345678901234").await;
     assert!(result.is_ok());
     let response = result.unwrap();
-    assert_eq!(response.uncashed_amount, "10000000000000000000");
+    assert_eq!(response.uncashed_amount, BZZ::from_plur_str("10000000000000000000").unwrap());
 }
 
 #[tokio::test]
@@ -99,7 +100,7 @@ async fn test_get_last_cheques_for_peer() {
             lastreceived: Some(Cheque {
                 beneficiary: "0x1234567890123456789012345678901234567890".to_string(),
                 chequebook: "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd".to_string(),
-                payout: "1000000000000000000".to_string(),
+                payout: BZZ::from_plur_str("1000000000000000000").unwrap(),
             }),
             lastsent: None,
         }))
@@ -125,7 +126,7 @@ async fn test_get_last_cheques() {
                     lastreceived: Some(Cheque {
                         beneficiary: "0x1234567890123456789012345678901234567890".to_string(),
                         chequebook: "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd".to_string(),
-                        payout: "1000000000000000000".to_string(),
+                        payout: BZZ::from_plur_str("1000000000000000000").unwrap(),
                     }),
                     lastsent: None,
                 },
@@ -153,7 +154,7 @@ async fn test_deposit_tokens() {
         .await;
 
     let client = BeeDebugChequebookClient::new(&mock_server.uri()).unwrap();
-    let result = client.deposit_tokens("1000000000000000000", None).await;
+    let result = client.deposit_tokens(&BZZ::from_plur(1_000_000_000_000_000_000u64), None).await;
     assert!(result.is_ok());
     let tx_hash = result.unwrap();
     assert_eq!(tx_hash, "0x1234567890123456789012345678901234567890123456789012345678901234");
@@ -171,7 +172,7 @@ async fn test_withdraw_tokens() {
         .await;
 
     let client = BeeDebugChequebookClient::new(&mock_server.uri()).unwrap();
-    let result = client.withdraw_tokens("1000000000000000000", None).await;
+    let result = client.withdraw_tokens(&BZZ::from_plur(1_000_000_000_000_000_000u64), None).await;
     assert!(result.is_ok());
     let tx_hash = result.unwrap();
     assert_eq!(tx_hash, "0x1234567890123456789012345678901234567890123456789012345678901234");
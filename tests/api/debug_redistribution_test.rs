@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use bee_rs::api::debug::redistribution::{
+    RedistributionError, RedistributionReporter, RedistributionRisk, SAMPLE_DURATION_LIMIT,
+};
+use wiremock::{matchers::{method, path}, Mock, MockServer, ResponseTemplate};
+
+fn redistribution_state(is_frozen: bool, is_fully_synced: bool, is_healthy: bool, sample_seconds: f64) -> serde_json::Value {
+    serde_json::json!({
+        "minimumGasFunds": "3000000000000000",
+        "hasSufficientFunds": true,
+        "isFrozen": is_frozen,
+        "isFullySynced": is_fully_synced,
+        "phase": "commit",
+        "round": 226812,
+        "lastWonRound": 226100,
+        "lastPlayedRound": 226780,
+        "lastFrozenRound": 226700,
+        "lastSelectedRound": 226780,
+        "lastSampleDurationSeconds": sample_seconds,
+        "block": 34734789,
+        "reward": "1000000000000000",
+        "fees": "1190437500000",
+        "isHealthy": is_healthy
+    })
+}
+
+async fn mock_node(state: serde_json::Value) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/redistributionstate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(state))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "overlay": "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f",
+            "proximity": 0,
+            "beeMode": "full",
+            "reserveSize": 3792587,
+            "reserveSizeWithinRadius": 3671223,
+            "pullsyncRate": 0.0,
+            "storageRadius": 10,
+            "connectedPeers": 171,
+            "neighborhoodSize": 7,
+            "batchCommitment": 137871360,
+            "isReachable": true,
+            "lastSyncedBlock": 34734789,
+            "committedDepth": 10,
+            "isWarmingUp": false
+        })))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+#[tokio::test]
+async fn test_healthy_node() {
+    let mock_server = mock_node(redistribution_state(false, true, true, 120.5)).await;
+
+    let reporter = RedistributionReporter::new(&mock_server.uri()).unwrap();
+    let report = reporter.report(false).await.unwrap();
+    assert!(!report.is_at_risk());
+    assert_eq!(report.storage_radius, 10);
+    assert_eq!(report.sample_duration, Duration::from_secs_f64(120.5));
+    assert_eq!(report.state.last_won_round, 226_100);
+}
+
+#[tokio::test]
+async fn test_node_at_risk() {
+    let mock_server = mock_node(redistribution_state(true, false, false, 100.0)).await;
+    Mock::given(method("GET"))
+        .and(path("/rchash/10/aaaa/aaaa"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "durationSeconds": 400.0 })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let reporter = RedistributionReporter::new(&mock_server.uri()).unwrap();
+    let report = reporter.report(true).await.unwrap();
+    assert!(report.is_at_risk());
+    assert_eq!(
+        report.risks,
+        vec![
+            RedistributionRisk::Frozen { last_frozen_round: 226_700 },
+            RedistributionRisk::NotFullySynced,
+            RedistributionRisk::Unhealthy { storage_radius: 10 },
+            RedistributionRisk::SlowSampling { duration: Duration::from_secs(400), limit: SAMPLE_DURATION_LIMIT },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_sampling_fails() {
+    let mock_server = mock_node(redistribution_state(false, true, true, 120.5)).await;
+    // Light nodes have no reserve to sample.
+    Mock::given(method("GET"))
+        .and(path("/rchash/10/aaaa/aaaa"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("Service Unavailable"))
+        .mount(&mock_server)
+        .await;
+
+    let reporter = RedistributionReporter::new(&mock_server.uri()).unwrap();
+    let result = reporter.report(true).await;
+    assert!(matches!(
+        result,
+        Err(RedistributionError::Reqwest(e)) if e.status() == Some(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+    ));
+}
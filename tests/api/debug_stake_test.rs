@@ -0,0 +1,114 @@
+use bee_rs::api::debug::stake::{BeeDebugStakeClient, RedistributionPhase};
+use bee_rs::utils::tokens::{BZZ, DAI};
+use wiremock::{matchers::{header, method, path}, Mock, MockServer, ResponseTemplate};
+
+const TX_HASH: &str = "0x1234567890123456789012345678901234567890123456789012345678901234";
+
+#[tokio::test]
+async fn test_get_stake() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/stake"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "stakedAmount": "100000000000000000"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = BeeDebugStakeClient::new(&mock_server.uri()).unwrap();
+    let response = client.get_stake().await.unwrap();
+    assert_eq!(response.staked_amount, BZZ::from_plur(100_000_000_000_000_000u64));
+}
+
+#[tokio::test]
+async fn test_deposit_stake() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/stake/100000000000000000"))
+        .and(header("gas-price", "100000000"))
+        .and(header("gas-limit", "210000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "txHash": TX_HASH })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = BeeDebugStakeClient::new(&mock_server.uri()).unwrap();
+    let amount = BZZ::from_plur(100_000_000_000_000_000u64);
+    let tx_hash = client.deposit_stake(&amount, Some("100000000"), Some("210000")).await.unwrap();
+    assert_eq!(tx_hash, TX_HASH);
+}
+
+#[tokio::test]
+async fn test_withdrawable_stake() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/stake/withdrawable"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "withdrawableAmount": "2500"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/stake/withdrawable"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "txHash": TX_HASH })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = BeeDebugStakeClient::new(&mock_server.uri()).unwrap();
+    let response = client.get_withdrawable_stake().await.unwrap();
+    assert_eq!(response.withdrawable_amount, BZZ::from_plur(2500u32));
+    let tx_hash = client.withdraw_surplus_stake(None, None).await.unwrap();
+    assert_eq!(tx_hash, TX_HASH);
+}
+
+#[tokio::test]
+async fn test_migrate_stake() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/stake"))
+        .and(header("gas-price", "100000000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "txHash": TX_HASH })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = BeeDebugStakeClient::new(&mock_server.uri()).unwrap();
+    let tx_hash = client.migrate_stake(Some("100000000"), None).await.unwrap();
+    assert_eq!(tx_hash, TX_HASH);
+}
+
+#[tokio::test]
+async fn test_get_redistribution_state() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/redistributionstate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "minimumGasFunds": "3000000000000000",
+            "hasSufficientFunds": true,
+            "isFrozen": false,
+            "isFullySynced": true,
+            "phase": "claim",
+            "round": 226812,
+            "lastWonRound": 0,
+            "lastPlayedRound": 226780,
+            "lastFrozenRound": 0,
+            "lastSelectedRound": 226780,
+            "lastSampleDurationSeconds": 371.842113624,
+            "block": 34734789,
+            "reward": "0",
+            "fees": "1190437500000",
+            "isHealthy": true
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = BeeDebugStakeClient::new(&mock_server.uri()).unwrap();
+    let state = client.get_redistribution_state().await.unwrap();
+    assert_eq!(state.minimum_gas_funds, DAI::from_wei(3_000_000_000_000_000u64));
+    assert_eq!(state.phase, RedistributionPhase::Claim);
+    assert_eq!(state.round, 226_812);
+    assert_eq!(state.last_played_round, 226_780);
+    assert_eq!(state.fees, DAI::from_wei(1_190_437_500_000u64));
+    assert!(state.is_healthy && state.is_fully_synced && !state.is_frozen);
+}
@@ -1,5 +1,11 @@
+use std::time::Duration;
+
+use bee_rs::api::typed_bytes::{BatchId, TransactionHash};
+use bee_rs::utils::tokens::BZZ;
+
 use bee_rs::api::debug::stamps::{
-    BeeDebugStampsClient, GlobalPostageBatch, PostageBatch, PostageBatchBucket, PostageBatchBuckets,
+    BatchFilter, BeeDebugStampsClient, BeeDebugStampsError, GlobalPostageBatch, PostageBatch,
+    PostageBatchBucket, PostageBatchBuckets,
 };
 use serde_json;
 use wiremock::{
@@ -12,19 +18,20 @@ async fn test_get_global_postage_batches() {
     let mock_server = MockServer::start().await;
     Mock::given(method("GET"))
         .and(path_regex("/batches"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
-            {
-                "batchID": "0x1234567890123456789012345678901234567890123456789012345678901234",
-                "value": "1000000000000000000",
-                "start": 0,
-                "depth": 0,
-                "bucketDepth": 0,
-                "immutableFlag": true,
-                "batchTTL": 0,
-                "owner": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd",
-                "storageRadius": 0
-            }
-        ])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "batches": [
+                {
+                    "batchID": "0x1234567890123456789012345678901234567890123456789012345678901234",
+                    "value": "1000000000000000000",
+                    "start": 0,
+                    "owner": "abcdefabcdefabcdefabcdefabcdefabcdefabcd",
+                    "depth": 20,
+                    "bucketDepth": 16,
+                    "immutable": true,
+                    "batchTTL": 0
+                }
+            ]
+        })))
         .mount(&mock_server)
         .await;
 
@@ -44,21 +51,23 @@ async fn test_get_all_postage_batches() {
     let mock_server = MockServer::start().await;
     Mock::given(method("GET"))
         .and(path_regex("/stamps"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
-            {
-                "batchID": "0x1234567890123456789012345678901234567890123456789012345678901234",
-                "utilization": 0,
-                "usable": true,
-                "label": "string",
-                "depth": 0,
-                "amount": "1000000000000000000",
-                "bucketDepth": 0,
-                "blockNumber": 0,
-                "immutableFlag": true,
-                "exists": true,
-                "batchTTL": 0
-            }
-        ])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "stamps": [
+                {
+                    "batchID": "0x1234567890123456789012345678901234567890123456789012345678901234",
+                    "utilization": 0,
+                    "usable": true,
+                    "label": "string",
+                    "depth": 20,
+                    "amount": "1000000000000000000",
+                    "bucketDepth": 16,
+                    "blockNumber": 0,
+                    "immutableFlag": true,
+                    "exists": true,
+                    "batchTTL": 0
+                }
+            ]
+        })))
         .mount(&mock_server)
         .await;
 
@@ -79,13 +88,12 @@ async fn test_get_postage_batch() {
     Mock::given(method("GET"))
         .and(path_regex("/stamps/(.*)"))
         .respond_with(ResponseTemplate::new(200).set_body_json(PostageBatch {
-            batch_id:
-                "0x1234567890123456789012345678901234567890123456789012345678901234".to_string(),
+            batch_id: "0x1234567890123456789012345678901234567890123456789012345678901234".parse().unwrap(),
             utilization: 100,
             usable: true,
-            label: Some("test_label".to_string()).unwrap(),
+            label: Some("test_label".to_string()),
             depth: 16,
-            amount: "1000000000000000000".to_string(),
+            amount: BZZ::from_plur(1_000_000_000_000_000_000u64),
             bucket_depth: 16,
             block_number: 12345,
             immutable_flag: true,
@@ -118,7 +126,7 @@ async fn test_get_postage_batch_buckets() {
             "bucketUpperBound": 100,
             "buckets": [
                 {
-                    "bucketId": 0,
+                    "bucketID": 0,
                     "collisions": 0
                 }
             ]
@@ -142,15 +150,16 @@ async fn test_create_postage_batch() {
     let mock_server = MockServer::start().await;
     Mock::given(method("POST"))
         .and(path_regex("/stamps/(.*)/(.*)"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "batchID": "0x1234567890123456789012345678901234567890123456789012345678901234"
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "batchID": "0x1234567890123456789012345678901234567890123456789012345678901234",
+            "txHash": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd"
         })))
         .mount(&mock_server)
         .await;
 
     let client = BeeDebugStampsClient::new(&mock_server.uri()).unwrap();
     let result = client
-        .create_postage_batch("1000000000000000000", 16, None, None, None)
+        .create_postage_batch(&BZZ::from_plur(1_000_000_000_000_000_000u64), 16, None, None, None)
         .await;
     assert!(result.is_ok());
     let batch_id = result.unwrap();
@@ -165,8 +174,9 @@ async fn test_top_up_batch() {
     let mock_server = MockServer::start().await;
     Mock::given(method("PATCH"))
         .and(path_regex("/stamps/topup/(.*)/(.*)"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "transactionHash": "0x1234567890123456789012345678901234567890123456789012345678901234"
+        .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({
+            "batchID": "abcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd",
+            "txHash": "0x1234567890123456789012345678901234567890123456789012345678901234"
         })))
         .mount(&mock_server)
         .await;
@@ -175,7 +185,7 @@ async fn test_top_up_batch() {
     let result = client
         .top_up_batch(
             "0x1234567890123456789012345678901234567890123456789012345678901234",
-            "1000000000000000000",
+            &BZZ::from_plur(1_000_000_000_000_000_000u64),
         )
         .await;
     assert!(result.is_ok());
@@ -191,8 +201,9 @@ async fn test_dilute_batch() {
     let mock_server = MockServer::start().await;
     Mock::given(method("PATCH"))
         .and(path_regex("/stamps/dilute/(.*)/(.*)"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "transactionHash": "0x1234567890123456789012345678901234567890123456789012345678901234"
+        .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({
+            "batchID": "abcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd",
+            "txHash": "0x1234567890123456789012345678901234567890123456789012345678901234"
         })))
         .mount(&mock_server)
         .await;
@@ -211,3 +222,147 @@ async fn test_dilute_batch() {
         "0x1234567890123456789012345678901234567890123456789012345678901234"
     );
 }
+
+fn stamp_json(batch_id: &str, label: &str, utilization: u32, usable: bool, ttl: i64) -> serde_json::Value {
+    serde_json::json!({
+        "batchID": batch_id,
+        "utilization": utilization,
+        "usable": usable,
+        "label": label,
+        "depth": 20,
+        "amount": "100000000",
+        "bucketDepth": 16,
+        "blockNumber": 12345,
+        "immutableFlag": true,
+        "exists": true,
+        "batchTTL": ttl,
+    })
+}
+
+#[tokio::test]
+async fn test_find_batch() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/stamps$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "stamps": [
+                stamp_json(&"aa".repeat(32), "uploads", 15, true, 90_000),
+                stamp_json(&"bb".repeat(32), "uploads", 2, true, 3_600),
+                stamp_json(&"cc".repeat(32), "uploads", 2, true, 86_400),
+                stamp_json(&"dd".repeat(32), "uploads", 0, false, 200_000),
+                stamp_json(&"ee".repeat(32), "backups", 0, true, 300_000),
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = BeeDebugStampsClient::new(&mock_server.uri()).unwrap();
+    let filter = |label: &str, min_ttl: Option<u64>, min_free_capacity: Option<u64>| BatchFilter {
+        label: Some(label.to_string()),
+        min_ttl: min_ttl.map(Duration::from_secs),
+        min_free_capacity,
+    };
+
+    let batch = client.find_batch(&BatchFilter::default()).await.unwrap().unwrap();
+    assert_eq!(batch.batch_id.as_str(), "ee".repeat(32));
+    let batch = client.find_batch(&filter("uploads", None, None)).await.unwrap().unwrap();
+    assert_eq!(batch.batch_id.as_str(), "aa".repeat(32));
+    // 1 free slot of 16 in the fullest bucket leaves 256 MiB.
+    assert_eq!(batch.remaining_capacity(), 256 * 1024 * 1024);
+    let batch = client.find_batch(&filter("uploads", None, Some(1 << 30))).await.unwrap().unwrap();
+    assert_eq!(batch.batch_id.as_str(), "cc".repeat(32));
+    let batch = client.find_batch(&filter("uploads", Some(86_400), Some(1 << 30))).await.unwrap().unwrap();
+    assert_eq!(batch.batch_id.as_str(), "cc".repeat(32));
+    assert!(client.find_batch(&filter("uploads", Some(100_000), None)).await.unwrap().is_none());
+    assert!(client.find_batch(&filter("other", None, None)).await.unwrap().is_none());
+}
+
+#[test]
+fn test_remaining_capacity() {
+    let batch: PostageBatch = serde_json::from_value(stamp_json(&"aa".repeat(32), "uploads", 16, true, 3_600)).unwrap();
+    assert_eq!(batch.remaining_capacity(), 0);
+
+    // Inconsistent batch data must not underflow.
+    let batch = PostageBatch {
+        depth: 8,
+        utilization: 0,
+        ..batch
+    };
+    assert_eq!(batch.remaining_capacity(), 0);
+}
+
+#[tokio::test]
+async fn test_wait_until_usable() {
+    let batch_id = "b330000000000000000000000000000000000000000000000000000000000000";
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(format!("/stamps/{}$", batch_id)))
+        .respond_with(ResponseTemplate::new(404))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(format!("/stamps/{}$", batch_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(stamp_json(batch_id, "", 0, true, 3_600)))
+        .with_priority(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = BeeDebugStampsClient::new(&mock_server.uri()).unwrap();
+    let batch = client.wait_until_usable(batch_id, Duration::from_secs(10)).await.unwrap();
+    assert!(batch.usable);
+
+    let result = client.wait_until_usable("unknown", Duration::from_millis(100)).await;
+    assert!(matches!(result, Err(BeeDebugStampsError::NotUsable(id, _)) if id == "unknown"));
+}
+
+#[test]
+fn test_deserialize_bee_payloads() {
+    // GET /stamps/{id} of a batch the node has not synced yet, without a label.
+    let batch: PostageBatch = serde_json::from_str(
+        r#"{"batchID":"f4d7cbbfd0c8ffdd2b3a4a37b6e8cd1e6f2b59e0cbcfd7d3e45a53d95ac36e83","utilization":0,"usable":false,"depth":21,"amount":"438000000","bucketDepth":16,"blockNumber":35427519,"immutableFlag":true,"exists":true,"batchTTL":-1}"#,
+    )
+    .unwrap();
+    assert_eq!(batch.batch_id.as_str(), "f4d7cbbfd0c8ffdd2b3a4a37b6e8cd1e6f2b59e0cbcfd7d3e45a53d95ac36e83");
+    assert_eq!(batch.label, None);
+    assert_eq!(batch.batch_ttl, -1);
+    assert_eq!(batch.ttl(), None);
+    assert_eq!(batch.block_number, 35_427_519);
+
+    let batch: PostageBatch = serde_json::from_str(
+        r#"{"batchID":"f4d7cbbfd0c8ffdd2b3a4a37b6e8cd1e6f2b59e0cbcfd7d3e45a53d95ac36e83","utilization":3,"usable":true,"label":"uploads","depth":21,"amount":"438000000","bucketDepth":16,"blockNumber":35427519,"immutableFlag":false,"exists":true,"batchTTL":5283547}"#,
+    )
+    .unwrap();
+    assert_eq!(batch.label.as_deref(), Some("uploads"));
+    assert_eq!(batch.ttl(), Some(Duration::from_secs(5_283_547)));
+
+    // GET /batches of Bee 2.x and of Bee 1.x, which still reported the storage radius.
+    let batch: GlobalPostageBatch = serde_json::from_str(
+        r#"{"batchID":"000de42079daebd58347bb38ce05bdc477701d93651d3bba318a9aee3fbd786a","value":"14633691312","start":25780238,"owner":"ab2b2ee6c2d0c6ea4d1f7d73e7dbf39cb1f7a8d4","depth":20,"bucketDepth":16,"immutable":true,"batchTTL":14461386}"#,
+    )
+    .unwrap();
+    assert!(batch.immutable_flag);
+    assert_eq!(batch.start, 25_780_238);
+    assert_eq!(batch.storage_radius, None);
+    let batch: GlobalPostageBatch = serde_json::from_str(
+        r#"{"batchID":"000de42079daebd58347bb38ce05bdc477701d93651d3bba318a9aee3fbd786a","value":"14633691312","start":25780238,"owner":"ab2b2ee6c2d0c6ea4d1f7d73e7dbf39cb1f7a8d4","depth":20,"bucketDepth":16,"immutableFlag":false,"batchTTL":-1,"storageRadius":10}"#,
+    )
+    .unwrap();
+    assert!(!batch.immutable_flag);
+    assert_eq!(batch.storage_radius, Some(10));
+
+    assert!(serde_json::from_str::<PostageBatch>(r#"{"batchID":"1234","utilization":0,"usable":true,"depth":20,"amount":"1","bucketDepth":16,"blockNumber":1,"immutableFlag":true,"exists":true,"batchTTL":1}"#).is_err());
+}
+
+#[test]
+fn test_typed_ids() {
+    let batch_id: BatchId = "0xF4D7CBBFD0C8FFDD2B3A4A37B6E8CD1E6F2B59E0CBCFD7D3E45A53D95AC36E83".parse().unwrap();
+    assert_eq!(batch_id.to_string(), "f4d7cbbfd0c8ffdd2b3a4a37b6e8cd1e6f2b59e0cbcfd7d3e45a53d95ac36e83");
+    assert_eq!(BatchId::from_bytes(&batch_id.to_bytes()).unwrap(), batch_id);
+    assert!("f4d7".parse::<BatchId>().is_err());
+
+    let tx_hash: TransactionHash = "1234567890123456789012345678901234567890123456789012345678901234".parse().unwrap();
+    assert_eq!(tx_hash.as_str(), "0x1234567890123456789012345678901234567890123456789012345678901234");
+    assert_eq!(serde_json::to_value(&tx_hash).unwrap(), serde_json::json!(tx_hash.as_str()));
+}
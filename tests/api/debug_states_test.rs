@@ -1,4 +1,5 @@
 use bee_rs::api::debug::states::{BeeDebugStatesClient, ChainState, ReserveState, WalletBalance};
+use bee_rs::utils::tokens::{BZZ, DAI};
 use wiremock::{matchers::{method, path_regex}, Mock, MockServer, ResponseTemplate};
 use serde_json;
 
@@ -30,8 +31,8 @@ async fn test_get_chain_state() {
         .respond_with(ResponseTemplate::new(200).set_body_json(ChainState {
             block: 100,
             chain_tip: 101,
-            total_amount: "100000000000000000000".to_string(),
-            current_price: "1.0".to_string(),
+            total_amount: BZZ::from_plur_str("100000000000000000000").unwrap(),
+            current_price: BZZ::from_plur(24000),
         }))
         .mount(&mock_server)
         .await;
@@ -41,7 +42,8 @@ async fn test_get_chain_state() {
     assert!(result.is_ok());
     let chain_state = result.unwrap();
     assert_eq!(chain_state.block, 100);
-    assert_eq!(chain_state.total_amount, "100000000000000000000");
+    assert_eq!(chain_state.total_amount, BZZ::from_plur_str("100000000000000000000").unwrap());
+    assert_eq!(chain_state.current_price, BZZ::from_plur(24000));
 }
 
 #[tokio::test]
@@ -63,6 +65,6 @@ async fn test_get_wallet_balance() {
     }
     assert!(result.is_ok());
     let wallet_balance = result.unwrap();
-    assert_eq!(wallet_balance.bzz_balance, "76946885095818311");
-    assert_eq!(wallet_balance.native_token_balance, "2999995780972583839");
+    assert_eq!(wallet_balance.bzz_balance, BZZ::from_plur(76946885095818311u64));
+    assert_eq!(wallet_balance.native_token_balance, DAI::from_wei(2999995780972583839u64));
 }
\ No newline at end of file
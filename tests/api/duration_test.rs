@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use bee_rs::utils::duration::{blocks_to_duration, duration_to_blocks, format_duration, parse_duration};
+use bee_rs::utils::stamps::BLOCK_TIME;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("1d 6h").unwrap(), Duration::from_secs(DAY + 6 * HOUR));
+    assert_eq!(parse_duration("1d6h").unwrap(), Duration::from_secs(DAY + 6 * HOUR));
+    assert_eq!(parse_duration("3 weeks").unwrap(), Duration::from_secs(21 * DAY));
+    assert_eq!(parse_duration("1 hour, 30 minutes").unwrap(), Duration::from_secs(90 * 60));
+    assert_eq!(parse_duration("1.5H").unwrap(), Duration::from_secs(90 * 60));
+    assert_eq!(parse_duration("0.1s").unwrap(), Duration::from_millis(100));
+    assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+
+    assert!(parse_duration("").is_err());
+    assert!(parse_duration("5").is_err());
+    assert!(parse_duration("5 fortnights").is_err());
+    assert!(parse_duration("-1h").is_err());
+    assert!(parse_duration("1..5h").is_err());
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_secs(DAY + 6 * HOUR)), "1d 6h");
+    assert_eq!(format_duration(Duration::from_secs(21 * DAY + 61)), "21d 1m 1s");
+    assert_eq!(format_duration(Duration::from_millis(1500)), "1s");
+    assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+    assert_eq!(format_duration(Duration::ZERO), "0s");
+
+    let duration = Duration::from_secs(3 * DAY + 59 * 60);
+    assert_eq!(parse_duration(&format_duration(duration)).unwrap(), duration);
+}
+
+#[test]
+fn test_blocks() {
+    assert_eq!(blocks_to_duration(17_280, BLOCK_TIME), Duration::from_secs(DAY));
+    assert_eq!(blocks_to_duration(u64::MAX, Duration::MAX), Duration::MAX);
+
+    assert_eq!(duration_to_blocks(Duration::from_secs(DAY), BLOCK_TIME).unwrap(), 17_280);
+    // A started block is a whole block.
+    assert_eq!(duration_to_blocks(Duration::from_secs(6), BLOCK_TIME).unwrap(), 2);
+    assert_eq!(duration_to_blocks(Duration::ZERO, BLOCK_TIME).unwrap(), 0);
+    assert!(duration_to_blocks(Duration::from_secs(DAY), Duration::ZERO).is_err());
+}
@@ -1,5 +1,7 @@
 use bee_rs::api::envelope::{post_envelope, EnvelopeWithBatchId};
 use wiremock::{matchers::{method, path_regex, header}, Mock, MockServer, ResponseTemplate};
+use bee_rs::api::signer::PrivateKeySigner;
+use bee_rs::api::stamper::Stamper;
 use serde_json;
 
 #[tokio::test]
@@ -39,3 +41,37 @@ async fn test_post_envelope() {
     assert_eq!(envelope.signature, expected_signature);
     assert_eq!(envelope.batch_id, expected_batch_id);
 }
+
+#[tokio::test]
+async fn test_verify_envelope() {
+    let batch_id = "b330000000000000000000000000000000000000000000000000000000000000";
+    let reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+    let signer = PrivateKeySigner::from_hex("634fb5a872396d9693e5c9f9d7233cfa93f395c093371017ff44aa9ae6564cdd").unwrap();
+    let mut stamper = Stamper::new(signer, batch_id, 20).unwrap();
+    let chunk_address: [u8; 32] = hex::decode(reference).unwrap().try_into().unwrap();
+    let stamp = stamper.stamp_at(&chunk_address, 1_700_000_000_000_000_000).unwrap();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex(format!("/envelope/{}", reference)))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "issuer": "8d3766440f0d7b949a5e32995d09619a7f86e632",
+            "index": hex::encode(stamp.index),
+            "timestamp": hex::encode(stamp.timestamp.to_be_bytes()),
+            "signature": hex::encode(stamp.signature),
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let envelope = post_envelope(&client, &mock_server.uri(), batch_id, reference).await.unwrap();
+
+    assert_eq!(envelope.stamp().unwrap(), stamp);
+    envelope.verify(reference).unwrap();
+    assert!(envelope.verify("00b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f").is_err());
+    assert!(envelope.verify("1234").is_err());
+
+    let mut other_issuer = envelope;
+    other_issuer.issuer = vec![0; 20];
+    assert!(other_issuer.verify(reference).is_err());
+}
@@ -0,0 +1,164 @@
+use bee_rs::api::manifest::{Manifest, CONTENT_TYPE_KEY, FILENAME_KEY};
+use bee_rs::api::mantaray::{MantarayNode, VERSION_02_HASH};
+use bee_rs::error::Error;
+use sha3::{Digest, Keccak256};
+use std::collections::BTreeMap;
+use wiremock::{
+    matchers::{method, path_regex},
+    Mock, MockServer, ResponseTemplate,
+};
+
+mod common;
+
+use common::start_store;
+
+const FILE_REFERENCE: &str = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+const OTHER_REFERENCE: &str = "1000000000000000000000000000000000000000000000000000000000000000";
+const LONG_PATH: &str = "assets/stylesheets/a-file-name-longer-than-a-fork-prefix.css";
+
+fn file_metadata(name: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        (CONTENT_TYPE_KEY.to_string(), "text/plain".to_string()),
+        (FILENAME_KEY.to_string(), name.to_string()),
+    ])
+}
+
+#[test]
+fn test_version_hash() {
+    let hash = Keccak256::digest(b"mantaray:0.2");
+    assert_eq!(VERSION_02_HASH[..], hash[..31]);
+}
+
+#[test]
+fn test_unmarshal_rejects_unknown_version() {
+    let data = vec![0u8; 128];
+    assert!(MantarayNode::unmarshal(&data).is_err());
+}
+
+#[tokio::test]
+async fn test_save_and_load_manifest() {
+    let (mock_server, _store) = start_store().await;
+    let client = reqwest::Client::new();
+
+    let mut manifest = Manifest::new(&client, &mock_server.uri());
+    manifest.add_file("index.html", FILE_REFERENCE, file_metadata("index.html")).await.unwrap();
+    manifest.add_file("img/logo.png", OTHER_REFERENCE, file_metadata("logo.png")).await.unwrap();
+    manifest.add_file("img/icon.png", FILE_REFERENCE, BTreeMap::new()).await.unwrap();
+    manifest.add_file(LONG_PATH, OTHER_REFERENCE, BTreeMap::new()).await.unwrap();
+    manifest.set_index_document("index.html").await.unwrap();
+    manifest.set_error_document("404.html").await.unwrap();
+    assert!(manifest.reference().is_none());
+
+    let reference = manifest.save("test_batch_id", None).await.unwrap();
+    assert_eq!(manifest.reference(), Some(reference.clone()));
+
+    let mut loaded = Manifest::load(&client, &mock_server.uri(), &reference).await.unwrap();
+    assert_eq!(loaded.index_document(), Some("index.html"));
+    assert_eq!(loaded.error_document(), Some("404.html"));
+
+    // Nodes below the root are only loaded when a path through them is used.
+    loaded.rename("img/logo.png", "img/brand.png").await.unwrap();
+    let node = loaded.root().find(b"img/brand.png").unwrap();
+    assert!(node.is_value());
+    assert_eq!(hex::encode(node.entry()), OTHER_REFERENCE);
+    assert_eq!(node.metadata().unwrap()[FILENAME_KEY], "brand.png");
+    assert!(loaded.root().find(b"img/logo.png").is_none());
+    assert!(loaded.root().find(b"img/icon.png").is_some());
+
+    loaded.remove(LONG_PATH).await.unwrap();
+    assert!(loaded.root().find(LONG_PATH.as_bytes()).is_none());
+}
+
+#[tokio::test]
+async fn test_save_uploads_only_changed_nodes() {
+    let (mock_server, store) = start_store().await;
+    let client = reqwest::Client::new();
+
+    let mut manifest = Manifest::new(&client, &mock_server.uri());
+    manifest.add_file("a/one.txt", FILE_REFERENCE, file_metadata("one.txt")).await.unwrap();
+    manifest.add_file("b/two.txt", FILE_REFERENCE, file_metadata("two.txt")).await.unwrap();
+    let reference = manifest.save("test_batch_id", None).await.unwrap();
+    let initial_uploads = store.uploads();
+
+    let mut loaded = Manifest::load(&client, &mock_server.uri(), &reference).await.unwrap();
+    loaded.add_file("a/one.txt", OTHER_REFERENCE, file_metadata("one.txt")).await.unwrap();
+    let updated = loaded.save("test_batch_id", None).await.unwrap();
+    assert_ne!(updated, reference);
+
+    // Only the root and the nodes along "a/one.txt" are uploaded again.
+    let uploads = store.uploads() - initial_uploads;
+    assert!(uploads < initial_uploads);
+
+    // Saving without changes does not upload anything.
+    assert_eq!(loaded.save("test_batch_id", None).await.unwrap(), updated);
+    assert_eq!(store.uploads() - initial_uploads, uploads);
+
+    let mut reloaded = Manifest::load(&client, &mock_server.uri(), &updated).await.unwrap();
+    reloaded.remove("b/two.txt").await.unwrap();
+    assert!(reloaded.root().find(b"b/two.txt").is_none());
+    assert!(reloaded.remove("b/two.txt").await.is_err());
+}
+
+#[tokio::test]
+async fn test_entries() {
+    let (mock_server, _store) = start_store().await;
+    let client = reqwest::Client::new();
+
+    let mut manifest = Manifest::new(&client, &mock_server.uri());
+    manifest.add_file("b.txt", OTHER_REFERENCE, BTreeMap::new()).await.unwrap();
+    manifest.add_file("a/nested/file.txt", FILE_REFERENCE, file_metadata("file.txt")).await.unwrap();
+    manifest.add_file("a/file.txt", OTHER_REFERENCE, BTreeMap::new()).await.unwrap();
+    manifest.set_index_document("b.txt").await.unwrap();
+    let reference = manifest.save("test_batch_id", None).await.unwrap();
+
+    let mut loaded = Manifest::load(&client, &mock_server.uri(), &reference).await.unwrap();
+    let entries = loaded.entries().await.unwrap();
+
+    let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+    assert_eq!(paths, vec!["a/file.txt", "a/nested/file.txt", "b.txt"]);
+    assert_eq!(entries[1].reference, FILE_REFERENCE);
+    assert_eq!(entries[1].metadata, file_metadata("file.txt"));
+}
+
+#[tokio::test]
+async fn test_remove_keeps_longer_paths() {
+    let (mock_server, _store) = start_store().await;
+    let client = reqwest::Client::new();
+
+    let mut manifest = Manifest::new(&client, &mock_server.uri());
+    manifest.add_file("docs", FILE_REFERENCE, BTreeMap::new()).await.unwrap();
+    manifest.add_file("docs/readme.md", OTHER_REFERENCE, BTreeMap::new()).await.unwrap();
+    manifest.remove("docs").await.unwrap();
+
+    assert!(!manifest.root().find(b"docs").unwrap().is_value());
+    let node = manifest.root().find(b"docs/readme.md").unwrap();
+    assert_eq!(hex::encode(node.entry()), OTHER_REFERENCE);
+}
+
+#[tokio::test]
+async fn test_load_missing_manifest() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/bytes/(.*)"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let result = Manifest::load(&client, &mock_server.uri(), FILE_REFERENCE).await;
+    assert!(matches!(result, Err(Error::NotFound(reference)) if reference == FILE_REFERENCE));
+}
+
+#[tokio::test]
+async fn test_load_fails_on_server_error() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("/bytes/(.*)"))
+        .respond_with(ResponseTemplate::new(500).set_body_bytes(vec![0u8; 128]))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let result = Manifest::load(&client, &mock_server.uri(), FILE_REFERENCE).await;
+    assert!(matches!(result, Err(Error::Reqwest(e)) if e.status() == Some(reqwest::StatusCode::INTERNAL_SERVER_ERROR)));
+}
@@ -0,0 +1,72 @@
+use bee_rs::api::bmt::calculate_chunk_address;
+use bee_rs::utils::merkle_tree::{file_address, file_address_from_path, FileHasher, BRANCHES, CHUNK_PAYLOAD_SIZE};
+
+mod common;
+
+fn chunk_address(span: u64, payload: &[u8]) -> [u8; 32] {
+    let mut content = span.to_le_bytes().to_vec();
+    content.extend_from_slice(payload);
+    calculate_chunk_address(&content).unwrap()
+}
+
+fn content(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn test_single_chunk() {
+    let data = b"hello world".to_vec();
+    assert_eq!(file_address(&data), chunk_address(data.len() as u64, &data));
+}
+
+#[test]
+fn test_empty_content() {
+    assert_eq!(file_address(&[]), chunk_address(0, &[]));
+}
+
+#[test]
+fn test_two_chunks() {
+    let data = content(CHUNK_PAYLOAD_SIZE + 1);
+    let first = chunk_address(CHUNK_PAYLOAD_SIZE as u64, &data[..CHUNK_PAYLOAD_SIZE]);
+    let second = chunk_address(1, &data[CHUNK_PAYLOAD_SIZE..]);
+    let expected = chunk_address(data.len() as u64, &[first, second].concat());
+
+    assert_eq!(file_address(&data), expected);
+}
+
+#[test]
+fn test_single_reference_is_carried() {
+    // A full intermediate chunk and a single leaf: the leaf is not wrapped
+    // into an intermediate chunk of its own.
+    let full = BRANCHES * CHUNK_PAYLOAD_SIZE;
+    let data = content(full + 1);
+    let leaves: Vec<[u8; 32]> = data
+        .chunks(CHUNK_PAYLOAD_SIZE)
+        .map(|chunk| chunk_address(chunk.len() as u64, chunk))
+        .collect();
+    let intermediate = chunk_address(full as u64, &leaves[..BRANCHES].concat());
+    let expected = chunk_address(data.len() as u64, &[intermediate, leaves[BRANCHES]].concat());
+
+    let mut hasher = FileHasher::recording_chunks();
+    hasher.update(&data);
+    let tree = hasher.finalize();
+    assert_eq!(tree.address, expected);
+    assert_eq!(tree.span, data.len() as u64);
+    assert_eq!(tree.chunk_addresses.len(), BRANCHES + 3);
+}
+
+#[tokio::test]
+async fn test_streaming_matches_one_shot() {
+    let data = content(3 * CHUNK_PAYLOAD_SIZE + 100);
+    let mut hasher = FileHasher::new();
+    for part in data.chunks(1000) {
+        hasher.update(part);
+    }
+    assert_eq!(hasher.finalize().address, file_address(&data));
+
+    let path = common::temp_path("merkle-tree");
+    std::fs::write(&path, &data).unwrap();
+    let address = file_address_from_path(&path).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(address, file_address(&data));
+}
@@ -0,0 +1,157 @@
+use bee_rs::api::bytes::upload_with_progress;
+use bee_rs::api::bzz::upload_file_with_progress;
+use bee_rs::api::progress::{watch_tag, ProgressObserver, UploadProgress};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::{
+    matchers::{header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const REFERENCE: &str = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+
+fn tag_json(synced: u64) -> serde_json::Value {
+    serde_json::json!({
+        "uid": 5,
+        "split": 4,
+        "seen": 0,
+        "stored": 4,
+        "sent": synced,
+        "synced": synced,
+        "address": REFERENCE,
+        "startedAt": "2024-05-01T12:00:00Z"
+    })
+}
+
+/// Collects the progress updates of an upload.
+fn recorder() -> (Arc<Mutex<Vec<UploadProgress>>>, Arc<dyn ProgressObserver>) {
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let recorded = updates.clone();
+    let observer = Arc::new(move |progress: UploadProgress| recorded.lock().unwrap().push(progress));
+    (updates, observer)
+}
+
+fn assert_sent_progress(updates: &[UploadProgress], total: u64) {
+    let sent: Vec<u64> = updates
+        .iter()
+        .filter_map(|update| match update {
+            UploadProgress::Sent { sent, total: t } => {
+                assert_eq!(*t, total);
+                Some(*sent)
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(sent.len() > 1);
+    assert!(sent.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(sent.last(), Some(&total));
+}
+
+#[tokio::test]
+async fn test_upload_with_progress() {
+    let data = vec![7u8; 200 * 1024];
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/bytes"))
+        .and(header("content-length", data.len().to_string().as_str()))
+        .and(header("swarm-postage-batch-id", "test_batch_id"))
+        .respond_with(ResponseTemplate::new(201)
+            .set_body_json(serde_json::json!({ "reference": REFERENCE }))
+            .insert_header("swarm-tag", "5"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/tags/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tag_json(1)))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let (updates, observer) = recorder();
+    let result = upload_with_progress(&client, &mock_server.uri(), data.clone(), "test_batch_id", None, observer)
+        .await
+        .unwrap();
+
+    assert_eq!(result.reference, REFERENCE);
+    assert_eq!(result.tag_uid, Some(5));
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, data);
+
+    let updates = updates.lock().unwrap();
+    assert_sent_progress(&updates, data.len() as u64);
+    match updates.last() {
+        Some(UploadProgress::Tag(tag)) => assert_eq!((tag.split, tag.stored, tag.synced), (4, 4, 1)),
+        other => panic!("expected tag progress, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_upload_file_with_progress() {
+    let data = vec![1u8; 100 * 1024];
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/bzz"))
+        .and(query_param("name", "data.bin"))
+        .and(header("content-length", data.len().to_string().as_str()))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "reference": REFERENCE })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let (updates, observer) = recorder();
+    let result = upload_file_with_progress(
+        &client,
+        &mock_server.uri(),
+        data.clone(),
+        "test_batch_id",
+        Some("data.bin"),
+        None,
+        observer,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.reference, REFERENCE);
+    let updates = updates.lock().unwrap();
+    assert_sent_progress(&updates, data.len() as u64);
+    // Without a tag there is nothing more to report.
+    assert!(matches!(updates.last(), Some(UploadProgress::Sent { .. })));
+}
+
+#[tokio::test]
+async fn test_watch_tag() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tags/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tag_json(2)))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/tags/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tag_json(4)))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let (updates, observer) = recorder();
+    let tag = watch_tag(&client, &mock_server.uri(), 5, Duration::from_secs(10), observer.as_ref())
+        .await
+        .unwrap();
+
+    assert!(tag.is_synced());
+    let synced: Vec<u64> = updates
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|update| match update {
+            UploadProgress::Tag(tag) => tag.synced,
+            other => panic!("expected tag progress, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(synced, vec![2, 4]);
+}
@@ -0,0 +1,37 @@
+use bee_rs::utils::size::Size;
+use bee_rs::utils::stamps::effective_volume;
+
+#[test]
+fn test_parse_size() {
+    assert_eq!("5GB".parse::<Size>().unwrap().bytes(), 5_000_000_000);
+    assert_eq!("512MiB".parse::<Size>().unwrap().bytes(), 512 << 20);
+    assert_eq!("512 mib".parse::<Size>().unwrap().bytes(), 512 << 20);
+    assert_eq!("1.5 TB".parse::<Size>().unwrap().bytes(), 1_500_000_000_000);
+    assert_eq!("0.5KiB".parse::<Size>().unwrap().bytes(), 512);
+    assert_eq!("4096".parse::<Size>().unwrap().bytes(), 4096);
+    assert_eq!("4096 B".parse::<Size>().unwrap().bytes(), 4096);
+
+    assert!("".parse::<Size>().is_err());
+    assert!("GB".parse::<Size>().is_err());
+    assert!("5 GiGs".parse::<Size>().is_err());
+    assert!("-5GB".parse::<Size>().is_err());
+    assert!("20000PB".parse::<Size>().is_err());
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(Size::from_bytes(5_000_000_000).to_string(), "5 GB");
+    assert_eq!(Size::from_bytes(512 << 20).to_string(), "536.87 MB");
+    assert_eq!(Size::from_bytes(1_500).to_string(), "1.5 kB");
+    assert_eq!(Size::from_bytes(999).to_string(), "999 B");
+}
+
+#[test]
+fn test_depth() {
+    assert_eq!("1MB".parse::<Size>().unwrap().depth().unwrap(), 22);
+    assert_eq!("5GB".parse::<Size>().unwrap().depth().unwrap(), 23);
+    assert_eq!("1TB".parse::<Size>().unwrap().depth().unwrap(), 28);
+    assert_eq!(Size::from_depth(24).unwrap().bytes(), effective_volume(24).unwrap());
+    assert_eq!(Size::from_depth(24).unwrap().depth().unwrap(), 24);
+    assert!(Size::from_depth(52).is_err());
+}
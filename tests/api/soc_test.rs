@@ -1,4 +1,6 @@
-use bee_rs::api::soc::upload;
+use bee_rs::api::soc::{upload, upload_with_stamp};
+use bee_rs::api::signer::PrivateKeySigner;
+use bee_rs::api::stamper::Stamper;
 use bee_rs::api::bytes::UploadOptions;
 use wiremock::{matchers::{method, path_regex, header, query_param}, Mock, MockServer, ResponseTemplate};
 use serde_json;
@@ -57,3 +59,35 @@ async fn test_upload_soc() {
     assert_eq!(upload_result.tag_uid, Some(expected_tag_uid));
     assert_eq!(upload_result.history_address, Some(expected_history_address.to_string()));
 }
+
+#[tokio::test]
+async fn test_upload_soc_with_stamp() {
+    let owner = "8d3766440f0d7b949a5e32995d09619a7f86e632";
+    let identifier = "abcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd";
+    let signature = "112233445566778899aabbccddeeff00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff00";
+    let expected_reference = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+
+    let signer = PrivateKeySigner::from_hex("634fb5a872396d9693e5c9f9d7233cfa93f395c093371017ff44aa9ae6564cdd").unwrap();
+    let mut stamper = Stamper::new(signer, "b330000000000000000000000000000000000000000000000000000000000000", 20).unwrap();
+    let stamp = stamper.stamp(&[7u8; 32]).unwrap();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex(format!("/soc/{}/{}", owner, identifier)))
+        .and(header("swarm-postage-stamp", hex::encode(stamp.to_bytes()).as_str()))
+        .and(query_param("sig", signature))
+        .respond_with(ResponseTemplate::new(201)
+            .set_body_json(serde_json::json!({ "reference": expected_reference }))
+            .insert_header("swarm-tag", "12"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let result = upload_with_stamp(&client, &mock_server.uri(), owner, identifier, signature, vec![1, 2, 3], &stamp, None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.reference, expected_reference);
+    assert_eq!(result.tag_uid, Some(12));
+}
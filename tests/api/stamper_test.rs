@@ -0,0 +1,113 @@
+use bee_rs::api::signer::{recover_address, PrivateKeySigner, Signer};
+use bee_rs::api::stamper::{bucket_of, stamp_digest, Stamp, Stamper, BUCKET_COUNT, STAMP_SIZE};
+
+mod common;
+
+// Test key from bee-js.
+const PRIVATE_KEY: &str = "634fb5a872396d9693e5c9f9d7233cfa93f395c093371017ff44aa9ae6564cdd";
+const OWNER: &str = "8d3766440f0d7b949a5e32995d09619a7f86e632";
+const BATCH_ID: &str = "b330000000000000000000000000000000000000000000000000000000000000";
+
+fn signer() -> PrivateKeySigner {
+    PrivateKeySigner::from_hex(PRIVATE_KEY).unwrap()
+}
+
+fn address(bucket: u16, last: u8) -> [u8; 32] {
+    let mut address = [last; 32];
+    address[..2].copy_from_slice(&bucket.to_be_bytes());
+    address
+}
+
+#[test]
+fn test_signer() {
+    let signer = signer();
+    assert_eq!(hex::encode(signer.address()), OWNER);
+
+    let signature = signer.sign(b"hello").unwrap();
+    assert!(signature[64] == 27 || signature[64] == 28);
+    assert_eq!(hex::encode(recover_address(b"hello", &signature).unwrap()), OWNER);
+    assert_ne!(hex::encode(recover_address(b"other", &signature).unwrap()), OWNER);
+
+    assert!(PrivateKeySigner::from_hex("1234").is_err());
+}
+
+#[test]
+fn test_stamp() {
+    let mut stamper = Stamper::new(signer(), BATCH_ID, 18).unwrap();
+    let chunk = address(0x1234, 1);
+
+    let first = stamper.stamp_at(&chunk, 1_700_000_000_000_000_000).unwrap();
+    let second = stamper.stamp_at(&chunk, 1_700_000_000_000_000_001).unwrap();
+
+    assert_eq!(bucket_of(&chunk), 0x1234);
+    assert_eq!(hex::encode(first.batch_id), BATCH_ID);
+    assert_eq!(first.index, [0, 0, 0x12, 0x34, 0, 0, 0, 0]);
+    assert_eq!((second.bucket(), second.slot()), (0x1234, 1));
+    assert_eq!(stamper.buckets()[0x1234], 2);
+
+    let digest = stamp_digest(&chunk, &first.batch_id, &first.index, first.timestamp);
+    assert_eq!(hex::encode(recover_address(&digest, &first.signature).unwrap()), OWNER);
+}
+
+#[test]
+fn test_serialize_and_verify_stamp() {
+    let mut stamper = Stamper::new(signer(), BATCH_ID, 18).unwrap();
+    let chunk = address(0x1234, 1);
+    let stamp = stamper.stamp_at(&chunk, 1_700_000_000_000_000_000).unwrap();
+
+    let bytes = stamp.to_bytes();
+    assert_eq!(bytes.len(), STAMP_SIZE);
+    assert_eq!(Stamp::from_bytes(&bytes).unwrap(), stamp);
+    assert_eq!(Stamp::try_from(&bytes[..]).unwrap(), stamp);
+    assert!(Stamp::from_bytes(&bytes[1..]).is_err());
+
+    let owner: [u8; 20] = hex::decode(OWNER).unwrap().try_into().unwrap();
+    assert_eq!(stamp.recover_owner(&chunk).unwrap(), owner);
+    stamp.verify(&chunk, &owner).unwrap();
+    assert!(stamp.verify(&chunk, &[0u8; 20]).is_err());
+    assert!(stamp.verify(&address(0x1234, 2), &owner).is_err());
+    assert!(stamp.verify(&address(0x1235, 1), &owner).is_err());
+
+    let mut tampered = stamp.clone();
+    tampered.timestamp += 1;
+    assert!(tampered.verify(&chunk, &owner).is_err());
+}
+
+#[test]
+fn test_bucket_full() {
+    // A batch of depth 17 holds two chunks per bucket.
+    let mut stamper = Stamper::new(signer(), BATCH_ID, 17).unwrap();
+    assert_eq!(stamper.bucket_capacity(), 2);
+
+    stamper.stamp(&address(7, 1)).unwrap();
+    stamper.stamp(&address(7, 2)).unwrap();
+    assert!(stamper.stamp(&address(7, 3)).is_err());
+    stamper.stamp(&address(8, 1)).unwrap();
+}
+
+#[test]
+fn test_invalid_stamper() {
+    assert!(Stamper::new(signer(), BATCH_ID, 16).is_err());
+    assert!(Stamper::new(signer(), "b330", 20).is_err());
+    assert!(Stamper::from_state(signer(), &[0u8; 10]).is_err());
+}
+
+#[tokio::test]
+async fn test_resume_from_state() {
+    let mut stamper = Stamper::new(signer(), BATCH_ID, 20).unwrap();
+    stamper.stamp(&address(3, 1)).unwrap();
+    stamper.stamp(&address(3, 2)).unwrap();
+    stamper.stamp(&address(65535, 1)).unwrap();
+
+    let path = common::temp_path("stamper");
+    stamper.save(&path).await.unwrap();
+    let mut resumed = Stamper::load(signer(), &path).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(resumed.depth(), 20);
+    assert_eq!(resumed.batch_id(), stamper.batch_id());
+    assert_eq!(resumed.buckets().len(), BUCKET_COUNT);
+    assert_eq!(resumed.buckets()[3], 2);
+    assert_eq!(resumed.buckets()[65535], 1);
+    assert_eq!(resumed.stamp(&address(3, 3)).unwrap().slot(), 2);
+}
@@ -0,0 +1,140 @@
+use bee_rs::api::debug::stamps::{PostageBatchBucket, PostageBatchBuckets};
+use bee_rs::utils::merkle_tree::FileHasher;
+use std::time::Duration;
+
+use bee_rs::utils::stamps::{
+    amount_for_ttl, batch_ttl, depth_for_volume, effective_volume, estimate_batch, simulate_upload, stamp_cost,
+    theoretical_volume, utilization_percent, BucketOverflow, BucketSimulation, BLOCK_TIME,
+};
+use bee_rs::utils::size::Size;
+use bee_rs::utils::tokens::BZZ;
+
+fn address(bucket: u16, last: u8) -> [u8; 32] {
+    let mut address = [last; 32];
+    address[..2].copy_from_slice(&bucket.to_be_bytes());
+    address
+}
+
+fn buckets(depth: u8, collisions: &[(u32, u32)]) -> PostageBatchBuckets {
+    PostageBatchBuckets {
+        depth,
+        bucket_depth: 16,
+        bucket_upper_bound: 1 << (depth - 16),
+        buckets: collisions
+            .iter()
+            .map(|&(bucket_id, collisions)| PostageBatchBucket { bucket_id, collisions })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_simulate_upload() {
+    let current = buckets(17, &[(7, 1), (9, 2)]);
+    let chunks = [address(7, 1), address(8, 1), address(8, 2)];
+
+    let simulation = simulate_upload(&current, &chunks).unwrap();
+    assert_eq!(simulation.bucket_capacity(), 2);
+    assert_eq!(&simulation.buckets()[7..10], &[2, 2, 2]);
+    assert_eq!(simulation.max_collisions(), 2);
+    assert!(simulation.fits());
+    assert!(simulation.overflows().is_empty());
+
+    let simulation = simulate_upload(&current, &[address(9, 1), address(9, 2), address(7, 3)]).unwrap();
+    assert!(!simulation.fits());
+    assert_eq!(
+        simulation.overflows(),
+        vec![BucketOverflow { bucket: 9, collisions: 4, capacity: 2 }]
+    );
+}
+
+#[test]
+fn test_simulate_chunked_upload() {
+    let mut hasher = FileHasher::recording_chunks();
+    let content: Vec<u8> = (0..4096 * 130).map(|i| (i * 7 / 4096 + i) as u8).collect();
+    hasher.update(&content);
+    let tree = hasher.finalize();
+    assert_eq!(tree.chunk_addresses.len(), 130 + 2 + 1);
+
+    let mut simulation = BucketSimulation::new(17, 16).unwrap();
+    simulation.add_all(&tree.chunk_addresses);
+    assert_eq!(simulation.buckets().iter().sum::<u32>(), 133);
+    assert!(simulation.fits());
+
+    // Identical chunks are stamped once.
+    let mut hasher = FileHasher::recording_chunks();
+    hasher.update(&vec![42u8; 4096 * 3]);
+    let tree = hasher.finalize();
+    let mut simulation = BucketSimulation::new(17, 16).unwrap();
+    simulation.add_all(&tree.chunk_addresses);
+    assert_eq!(simulation.buckets().iter().sum::<u32>(), 2);
+}
+
+#[test]
+fn test_bucket_of_depth() {
+    let simulation = BucketSimulation::new(20, 8).unwrap();
+    assert_eq!(simulation.bucket_capacity(), 4096);
+    assert_eq!(simulation.buckets().len(), 256);
+    assert_eq!(simulation.bucket_of(&address(0xab12, 0)), 0xab);
+
+    assert!(BucketSimulation::new(15, 16).is_err());
+    assert!(BucketSimulation::from_buckets(&buckets(17, &[(1 << 16, 1)])).is_err());
+}
+
+#[test]
+fn test_utilization_percent() {
+    assert_eq!(utilization_percent(0, 20, 16), 0.0);
+    assert_eq!(utilization_percent(4, 20, 16), 25.0);
+    assert_eq!(utilization_percent(16, 20, 16), 100.0);
+}
+
+#[test]
+fn test_volume() {
+    assert_eq!(theoretical_volume(20).unwrap(), 4 * 1024 * 1024 * 1024);
+    assert_eq!(effective_volume(21).unwrap(), 0);
+    // 4.93 GB at depth 22 and 1 TB at depth 28, as listed by bee-js.
+    assert_eq!(effective_volume(22).unwrap() / 10_000_000, 492);
+    assert_eq!(effective_volume(28).unwrap() / 10_000_000_000, 100);
+    assert_eq!(
+        effective_volume(40).unwrap(),
+        (theoretical_volume(40).unwrap() as f64 * 0.99) as u64
+    );
+
+    // The volume of deeper batches does not fit into a u64.
+    assert_eq!(theoretical_volume(51).unwrap(), 1 << 63);
+    assert!(theoretical_volume(52).is_err());
+    assert!(theoretical_volume(64).is_err());
+    assert!(effective_volume(52).is_err());
+    assert!(effective_volume(u8::MAX).is_err());
+}
+
+#[test]
+fn test_ttl() {
+    let price = BZZ::from_plur(24_000u32);
+    let day = Duration::from_secs(24 * 60 * 60);
+
+    let amount = amount_for_ttl(day, &price, BLOCK_TIME).unwrap();
+    assert_eq!(amount, BZZ::from_plur(17_280u32 * 24_000));
+    assert_eq!(batch_ttl(&amount, &price, BLOCK_TIME).unwrap(), day);
+
+    // Partial blocks are rounded up when buying and down when estimating.
+    let amount = amount_for_ttl(Duration::from_secs(7), &price, BLOCK_TIME).unwrap();
+    assert_eq!(amount, BZZ::from_plur(48_000u32));
+    assert_eq!(batch_ttl(&BZZ::from_plur(47_999u32), &price, BLOCK_TIME).unwrap(), Duration::from_secs(5));
+
+    assert!(batch_ttl(&amount, &BZZ::from_plur(0u32), BLOCK_TIME).is_err());
+    assert_eq!(stamp_cost(20, &BZZ::from_plur(3u32)), BZZ::from_plur(3u32 << 20));
+}
+
+#[test]
+fn test_estimate_batch() {
+    assert_eq!(depth_for_volume(0).unwrap(), 22);
+    assert_eq!(depth_for_volume(effective_volume(22).unwrap()).unwrap(), 22);
+    assert_eq!(depth_for_volume(effective_volume(22).unwrap() + 1).unwrap(), 23);
+    assert!(depth_for_volume(u64::MAX).is_err());
+
+    let size: Size = "5GB".parse().unwrap();
+    let estimate = estimate_batch(size, Duration::from_secs(24 * 60 * 60), &BZZ::from_plur(24_000u32), BLOCK_TIME).unwrap();
+    assert_eq!(estimate.depth, 23);
+    assert_eq!(estimate.amount, BZZ::from_plur(17_280u32 * 24_000));
+    assert_eq!(estimate.cost, stamp_cost(23, &estimate.amount));
+}
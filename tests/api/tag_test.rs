@@ -0,0 +1,145 @@
+use bee_rs::api::tag::{create_tag, delete_tag, get_tag, list_tags, update_tag, wait_for_sync};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::{
+    matchers::{body_json, method, path, query_param},
+    Mock, MockServer, Request, Respond, ResponseTemplate,
+};
+
+const REFERENCE: &str = "36b7efd913ca4cf880b8eeac5093fa27b0825906c600685b6abdd6566e6cfe8f";
+
+fn tag_json(uid: u32, split: u64, seen: u64, synced: u64) -> serde_json::Value {
+    serde_json::json!({
+        "uid": uid,
+        "split": split,
+        "seen": seen,
+        "stored": split,
+        "sent": synced,
+        "synced": synced,
+        "address": REFERENCE,
+        "startedAt": "2024-05-01T12:00:00Z"
+    })
+}
+
+/// Reports one more synced chunk on every request.
+#[derive(Clone, Default)]
+struct SyncingTag {
+    synced: Arc<AtomicU64>,
+}
+
+impl Respond for SyncingTag {
+    fn respond(&self, _request: &Request) -> ResponseTemplate {
+        let synced = self.synced.fetch_add(1, Ordering::SeqCst);
+        ResponseTemplate::new(200).set_body_json(tag_json(7, 3, 1, synced))
+    }
+}
+
+#[tokio::test]
+async fn test_create_tag() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tags"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(tag_json(7, 0, 0, 0)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let tag = create_tag(&client, &mock_server.uri()).await.unwrap();
+
+    assert_eq!(tag.uid, 7);
+    assert_eq!(tag.started_at, "2024-05-01T12:00:00Z");
+    assert!(!tag.is_synced());
+}
+
+#[tokio::test]
+async fn test_get_and_list_tags() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tags/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tag_json(7, 10, 2, 8)))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/tags"))
+        .and(query_param("offset", "5"))
+        .and(query_param("limit", "2"))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_json(serde_json::json!({ "tags": [tag_json(7, 10, 2, 8), tag_json(8, 1, 0, 0)] })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/tags/9"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    let tag = get_tag(&client, base_url, 7).await.unwrap();
+    assert_eq!((tag.split, tag.seen, tag.stored, tag.sent, tag.synced), (10, 2, 10, 8, 8));
+    assert_eq!(tag.address, REFERENCE);
+    assert!(tag.is_synced());
+
+    let tags = list_tags(&client, base_url, Some(5), Some(2)).await.unwrap();
+    assert_eq!(tags.iter().map(|tag| tag.uid).collect::<Vec<_>>(), vec![7, 8]);
+
+    assert!(get_tag(&client, base_url, 9).await.is_err());
+}
+
+#[tokio::test]
+async fn test_update_and_delete_tag() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/tags/7"))
+        .and(body_json(serde_json::json!({ "address": REFERENCE })))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/tags/7"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let base_url = &mock_server.uri();
+
+    update_tag(&client, base_url, 7, REFERENCE).await.unwrap();
+    delete_tag(&client, base_url, 7).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_wait_for_sync() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tags/7"))
+        .respond_with(SyncingTag::default())
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let tag = wait_for_sync(&client, &mock_server.uri(), 7, Duration::from_secs(10)).await.unwrap();
+
+    // Two chunks have to be synced, the third one was already seen.
+    assert_eq!(tag.synced, 2);
+}
+
+#[tokio::test]
+async fn test_wait_for_sync_timeout() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tags/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tag_json(7, 3, 0, 1)))
+        .mount(&mock_server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let result = wait_for_sync(&client, &mock_server.uri(), 7, Duration::from_millis(100)).await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,69 @@
+use bee_rs::api::bzz::CollectionEntry;
+use bee_rs::utils::tar_writer::write_tar;
+use futures::TryStreamExt;
+use std::io::Read;
+
+mod common;
+
+const LONG_PATH: &str = "assets/some/deeply/nested/directory/structure/that/does/not/fit/into/a/tar/header/file-name.txt";
+
+async fn collect(entries: Vec<CollectionEntry>) -> (u64, Vec<u8>) {
+    let archive = write_tar(entries).unwrap();
+    let chunks: Vec<bytes::Bytes> = archive.stream.try_collect().await.unwrap();
+    (archive.size, chunks.concat())
+}
+
+fn read_entries(data: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut archive = tar::Archive::new(data);
+    archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).unwrap();
+            (path, content)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_write_tar() {
+    let entries = vec![
+        CollectionEntry::from_data("index.html", b"<html></html>".to_vec()),
+        CollectionEntry::from_data("empty.txt", Vec::new()),
+        CollectionEntry::from_data(LONG_PATH, vec![7u8; 1000]),
+    ];
+
+    let (size, data) = collect(entries).await;
+
+    assert_eq!(size, data.len() as u64);
+    assert_eq!(data.len() % 512, 0);
+    let entries = read_entries(&data);
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0], ("index.html".to_string(), b"<html></html>".to_vec()));
+    assert_eq!(entries[1], ("empty.txt".to_string(), Vec::new()));
+    assert_eq!(entries[2], (LONG_PATH.to_string(), vec![7u8; 1000]));
+}
+
+#[tokio::test]
+async fn test_write_tar_from_file() {
+    let file = common::temp_path("tar-test");
+    let content: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    std::fs::write(&file, &content).unwrap();
+
+    let entry = CollectionEntry::from_file("data/file.bin", &file).unwrap();
+    let (size, data) = collect(vec![entry]).await;
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(size, data.len() as u64);
+    assert_eq!(read_entries(&data), vec![("data/file.bin".to_string(), content)]);
+}
+
+#[tokio::test]
+async fn test_write_tar_rejects_size_mismatch() {
+    let mut entry = CollectionEntry::from_data("index.html", vec![1, 2, 3]);
+    entry.size = 4;
+    assert!(write_tar(vec![entry]).is_err());
+}
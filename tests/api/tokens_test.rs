@@ -0,0 +1,56 @@
+use bee_rs::utils::fixed_point_number::FixedPointNumber;
+use bee_rs::utils::tokens::{BZZ, DAI};
+
+#[test]
+fn test_decimal_strings() {
+    let amount = BZZ::from_decimal_str("1.5").unwrap();
+    assert_eq!(amount, BZZ::from_plur(15_000_000_000_000_000u64));
+    assert_eq!(amount.to_decimal_string(), "1.5");
+    assert_eq!(amount.to_string(), "1.5 BZZ");
+    assert_eq!("1.5 BZZ".parse::<BZZ>().unwrap(), amount);
+
+    assert_eq!(BZZ::from_plur(1u32).to_decimal_string(), "0.0000000000000001");
+    assert_eq!(BZZ::from_decimal_str(".25").unwrap().to_decimal_string(), "0.25");
+    assert_eq!(DAI::from_decimal_str("2").unwrap(), DAI::from_wei(2_000_000_000_000_000_000u64));
+    assert_eq!(DAI::from_wei(1u32).to_string(), "0.000000000000000001 DAI");
+
+    assert!(BZZ::from_decimal_str("0.00000000000000001").is_err());
+    assert!(BZZ::from_decimal_str("1,5").is_err());
+    assert!(BZZ::from_decimal_str("").is_err());
+    assert!(BZZ::from_plur_str("1.5").is_err());
+
+    let number = FixedPointNumber::from_decimal_str("-12.3400", 4).unwrap();
+    assert_eq!(number.value(), &(-123_400).into());
+    assert_eq!(number.to_string(), "-12.34");
+}
+
+#[test]
+fn test_arithmetic() {
+    let one = BZZ::from_decimal_str("1").unwrap();
+    let half = BZZ::from_decimal_str("0.5").unwrap();
+
+    assert_eq!(&one + &half, BZZ::from_decimal_str("1.5").unwrap());
+    assert_eq!(half.clone() - one.clone(), BZZ::from_decimal_str("-0.5").unwrap());
+    assert_eq!(&half * 4, BZZ::from_decimal_str("2").unwrap());
+    assert_eq!(one.clone() / 3, BZZ::from_plur(3_333_333_333_333_333u64));
+    assert_eq!(vec![one.clone(), half.clone(), half.clone()].into_iter().sum::<BZZ>(), &one * 2);
+
+    assert!(half < one);
+    assert!((-one).is_negative());
+    assert!(BZZ::default().is_zero());
+}
+
+#[test]
+fn test_serde() {
+    let amount: BZZ = serde_json::from_str(r#""100000000000000000000""#).unwrap();
+    assert_eq!(amount.to_decimal_string(), "10000");
+    assert_eq!(serde_json::to_string(&amount).unwrap(), r#""100000000000000000000""#);
+
+    // Peer balances are negative when the node owes the peer.
+    let balance: BZZ = serde_json::from_str(r#""-2500""#).unwrap();
+    assert_eq!(balance, BZZ::from_plur(-2500));
+
+    let balance: DAI = serde_json::from_str("42").unwrap();
+    assert_eq!(balance, DAI::from_wei(42u32));
+    assert!(serde_json::from_str::<DAI>(r#""1.5""#).is_err());
+}